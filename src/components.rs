@@ -11,7 +11,9 @@ pub struct Protagonist {
     pub is_dirigible: bool,
     pub is_outside: bool,
     pub is_birds_eye: bool,
+    pub is_first_person: bool,
     pub is_jumping: bool,
+    pub is_crawling: bool,
     pub last_climb_toggle: f32,
 }
 
@@ -27,7 +29,9 @@ impl Default for Protagonist {
             is_dirigible: false,
             is_outside: true,
             is_birds_eye: false,
+            is_first_person: false,
             is_jumping: false,
+            is_crawling: false,
             last_climb_toggle: 0.0,
         }
     }
@@ -36,16 +40,121 @@ impl Default for Protagonist {
 #[derive(Component)]
 pub struct HighAltitudeIndicator;
 
+#[derive(Component)]
+pub struct Health {
+    pub current: f32,
+    pub max: f32,
+}
+
+impl Health {
+    pub fn new(max: f32) -> Self {
+        Self { current: max, max }
+    }
+
+    pub fn fraction(&self) -> f32 {
+        (self.current / self.max).clamp(0.0, 1.0)
+    }
+}
+
+#[derive(Component)]
+pub struct Fuel {
+    pub current: f32,
+    pub max: f32,
+}
+
+impl Fuel {
+    pub fn new(max: f32) -> Self {
+        Self { current: max, max }
+    }
+
+    pub fn fraction(&self) -> f32 {
+        (self.current / self.max).clamp(0.0, 1.0)
+    }
+}
+
+// A stack of one item type, keyed by the item id from the item catalog
+#[derive(Debug, Clone)]
+pub struct ItemStack {
+    pub item: String,
+    pub count: u32,
+}
+
+#[derive(Component, Default, Debug)]
+pub struct Inventory {
+    pub stacks: Vec<ItemStack>,
+    pub selected: usize,
+}
+
+impl Inventory {
+    // Add up to `count` items, returning how many didn't fit in the stack
+    pub fn add(&mut self, item: &str, count: u32, max_stack: u32) -> u32 {
+        let index = self.stacks.iter().position(|stack| stack.item == item);
+        let held = index.map_or(0, |index| self.stacks[index].count);
+        let added = count.min(max_stack.saturating_sub(held));
+        if added == 0 {
+            return count;
+        }
+
+        match index {
+            Some(index) => self.stacks[index].count += added,
+            None => self.stacks.push(ItemStack { item: item.to_string(), count: added }),
+        }
+        count - added
+    }
+
+    pub fn count(&self, item: &str) -> u32 {
+        self.stacks
+            .iter()
+            .find(|stack| stack.item == item)
+            .map_or(0, |stack| stack.count)
+    }
+
+    // Remove `count` items if we have that many, dropping empty stacks
+    pub fn remove(&mut self, item: &str, count: u32) -> bool {
+        let Some(index) = self.stacks.iter().position(|stack| stack.item == item) else {
+            return false;
+        };
+        if self.stacks[index].count < count {
+            return false;
+        }
+
+        self.stacks[index].count -= count;
+        if self.stacks[index].count == 0 {
+            self.stacks.remove(index);
+            if self.selected >= self.stacks.len() {
+                self.selected = 0;
+            }
+        }
+        true
+    }
+
+    pub fn selected_item(&self) -> Option<&str> {
+        self.stacks.get(self.selected).map(|stack| stack.item.as_str())
+    }
+
+    pub fn select_next(&mut self) {
+        if !self.stacks.is_empty() {
+            self.selected = (self.selected + 1) % self.stacks.len();
+        }
+    }
+}
+
 #[derive(Component)]
 pub struct Sentry {
     pub view_distance: f32,
     pub view_angle: f32,
     pub follow_speed: f32,
     pub velocity: Vec3,
+    pub last_heard: Option<Vec3>,  // Position of the last noise this sentry heard
+    pub tracking: Option<f32>,  // When the footprint it is following was left
 }
 
-#[derive(Component)]
-pub struct Tank;
+// A drivable tank. It stays parked wherever its driver climbs out.
+#[derive(Component, Default)]
+pub struct Tank {
+    pub driver: Option<Entity>,
+    pub unattended: bool,  // Left behind by its driver and not yet noticed by a sentry
+}
 
 #[derive(Component)]
 pub struct MazeMessageTrigger {
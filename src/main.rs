@@ -11,11 +11,33 @@ use crate::resources::{ProtagonistAnimations, PROTAGONIST_ANIMATIONS};
 use crate::systems::core::screenplay::MessageDisplay;
 
 use systems::core::setup::setup;
-use systems::core::camera::rotate_camera;
+use systems::core::camera::{
+    rotate_camera,
+    orbit_camera_input,
+    tag_protagonist_meshes,
+    update_first_person_layers,
+    CameraOrbit,
+};
 use systems::core::keyboard_input::keyboard_animation_control;
 use systems::core::timer::{setup_debug_timer, print_protagonist_transform};
 use systems::core::minimap::{setup_minimap, update_minimap, update_sentry_markers};
 use systems::core::screenplay::{setup_screenplay, screenplay_system};
+use systems::core::cinematics::{
+    start_camera_shots,
+    play_camera_shot,
+    cinematic_inactive,
+    CinematicState,
+    PlayCameraShot,
+    load_camera_shots,
+};
+use systems::core::interaction::{
+    setup_interaction_prompt,
+    find_interaction_focus,
+    update_interaction_prompt,
+    interaction_input,
+    InteractionFocus,
+    InteractEvent,
+};
 
 use systems::player::climbing::{
     handle_climbing, 
@@ -24,21 +46,94 @@ use systems::player::climbing::{
     handle_ladder_top,
 };
 use systems::player::swimming::swimming_system;
-use systems::player::driving::{toggle_driving, driving_control };
+use systems::player::driving::{
+    toggle_driving,
+    driving_control,
+    handle_tank_entry,
+    handle_tank_exit,
+    seat_driver,
+    tank_suspension,
+    update_tank_headlights,
+    consume_tank_fuel,
+    damage_tanks_from_explosions,
+    setup_tank_hud,
+    update_tank_hud,
+    fire_tank_emp,
+    ExitTankEvent,
+};
+use systems::player::footprints::{
+    setup_footprints,
+    leave_footprints,
+    leave_tank_tracks,
+    fade_footprints,
+    sentries_follow_footprints,
+};
 use systems::player::teleports::teleport_system;
 use systems::player::falling;
-use systems::player::dirigible::{toggle_dirigible, dirigible_control, animate_floating_balloon};
+use systems::player::dirigible::{
+    toggle_dirigible,
+    dirigible_control,
+    dirigible_ballast_control,
+    handle_balloon_boarding,
+    animate_tethered_balloons,
+};
+use systems::player::inventory::{
+    load_item_catalog,
+    spawn_pickups,
+    setup_inventory_hud,
+    animate_pickups,
+    collect_pickups,
+    use_selected_item,
+    update_flares,
+    update_noise_makers,
+    update_inventory_hud,
+};
 
 use systems::environments::acquifier::check_acquifier_dirigible_trigger;
 use systems::environments::portal::portal_system;
-use systems::environments::terrain::{spawn_terrain, toggle_terrain_texture};
-use systems::environments::airlock::{spawn_airlock, handle_airlock_teleport, blink_airlock_light};
+use systems::environments::terrain::{
+    spawn_terrain,
+    toggle_terrain_texture,
+    stream_terrain_chunks,
+    finish_terrain_chunk_tasks,
+    TerrainHeightfield,
+};
+use systems::environments::airlock::{spawn_airlock, blink_airlock_light};
+use systems::environments::doors::{handle_door_interaction, animate_doors, pass_through_doors};
 use systems::environments::searchlight::{underwater_searchlight_system, update_searchlight_rotation};
-use systems::environments::maze::{spawn_maze, check_dirigible_trigger, check_tank_exit};
-use systems::environments::ice_cave::{update_ice_particles, handle_ice_cave_interactions};
-use systems::environments::garage::{spawn_garages, handle_tank_interaction, handle_garage_approach};
+use systems::environments::maze::{spawn_maze, check_dirigible_trigger, check_tank_exit, request_maze_hint, update_maze_hint_arrows, MazeConfig};
+use systems::environments::ice_cave::{spawn_ice_particles, update_ice_particles, handle_ice_cave_interactions, IceParticleSettings};
+use systems::environments::garage::{
+    spawn_garages,
+    handle_tank_interaction,
+    handle_garage_approach,
+    service_tanks_in_garages,
+};
 use systems::environments::big_pipe::{spawn_big_pipe, handle_pipe_lift};
-use systems::environments::lanterns::{spawn_lanterns, update_lanterns};
+use systems::environments::lanterns::{spawn_lanterns, update_lanterns, update_lantern_lights};
+use systems::environments::time_of_day::{update_time_of_day, time_of_day_controls, TimeOfDay};
+use systems::environments::lighting::{apply_lighting, LightingStack};
+use systems::environments::wind::{update_wind, WindField};
+use systems::environments::weather::{update_weather, apply_weather_fog, spawn_snowfall, update_snowfall, Weather};
+use systems::environments::surface::{
+    apply_surface_friction,
+    update_ground_surface,
+    footstep_noise,
+};
+use systems::environments::tram::{
+    spawn_tram_network,
+    move_trams,
+    carry_tram_riders,
+    handle_tram_interaction,
+    update_tram_prompts,
+};
+use systems::environments::reactor::{
+    setup_sabotage_hud,
+    plant_reactor_charge,
+    update_reactor_sabotage,
+    PlantChargeEvent,
+    ReactorSabotage,
+};
 
 use systems::core::sentry::{
     spawn_sentry,
@@ -48,6 +143,12 @@ use systems::core::sentry::{
     periodic_sentry_spawn,
     setup_explosion_materials,
     animate_light_cones,
+    sentry_hear_noise,
+    sentry_notice_empty_tanks,
+    recover_stunned_sentries,
+    NoiseEvent,
+    SentryExplosionEvent,
+    SentrySurge,
 };
 
 use avian3d::prelude::*;
@@ -57,7 +158,6 @@ use bevy::{
     prelude::*,
 };
 // use bevy_inspector_egui::quick::WorldInspectorPlugin;
-use std::f32::consts::*;
 use std::time::Duration;
 
 fn main() {
@@ -69,6 +169,25 @@ fn main() {
             // WorldInspectorPlugin::new(),
         ))
         .init_resource::<MessageDisplay>()
+        .init_resource::<CameraOrbit>()
+        .init_resource::<CinematicState>()
+        .init_resource::<InteractionFocus>()
+        .init_resource::<SentrySurge>()
+        .init_resource::<ReactorSabotage>()
+        .init_resource::<WindField>()
+        .init_resource::<Weather>()
+        .init_resource::<TimeOfDay>()
+        .init_resource::<LightingStack>()
+        .init_resource::<IceParticleSettings>()
+        .init_resource::<TerrainHeightfield>()
+        .init_resource::<MazeConfig>()
+        .add_event::<NoiseEvent>()
+        .add_event::<falling::LandingEvent>()
+        .add_event::<PlayCameraShot>()
+        .add_event::<InteractEvent>()
+        .add_event::<PlantChargeEvent>()
+        .add_event::<ExitTankEvent>()
+        .add_event::<SentryExplosionEvent>()
         .add_systems(Startup, (
             setup,
             setup_explosion_materials,
@@ -79,39 +198,63 @@ fn main() {
         .add_systems(Startup, spawn_maze)
         .add_systems(Startup, spawn_terrain)
         .add_systems(Startup, spawn_airlock)
-        .add_systems(Update, handle_airlock_teleport)
+        .add_systems(Update, (handle_door_interaction, animate_doors, pass_through_doors).chain())
         .add_systems(Update, check_acquifier_dirigible_trigger)
         .add_systems(Update, blink_airlock_light)
         .add_systems(Update, toggle_terrain_texture)
-        .add_systems(Update, toggle_driving)
-        .add_systems(Update, driving_control)
-        .add_systems(Update, check_tank_exit)
-        .add_systems(Update, toggle_dirigible)
-        .add_systems(Update, dirigible_control)
-        .add_systems(Update, animate_floating_balloon)
-        .add_systems(Update, animate_light_direction)
-        .add_systems(Update, rotate_camera)
+        .add_systems(Update, (request_maze_hint.run_if(cinematic_inactive), update_maze_hint_arrows))
+        .add_systems(Update, (stream_terrain_chunks, finish_terrain_chunk_tasks).chain())
+        .add_systems(Update, (
+            check_tank_exit,
+            toggle_driving.run_if(cinematic_inactive),
+            handle_tank_exit,
+            tank_suspension,
+            driving_control.run_if(cinematic_inactive),
+            seat_driver,
+        ).chain())
+        .add_systems(Update, update_tank_headlights)
+        .add_systems(Startup, setup_tank_hud)
+        .add_systems(Update, (
+            consume_tank_fuel,
+            damage_tanks_from_explosions,
+            service_tanks_in_garages,
+            fire_tank_emp.run_if(cinematic_inactive),
+            update_tank_hud,
+        ))
+        .add_systems(Update, toggle_dirigible.run_if(cinematic_inactive))
+        .add_systems(Update, (dirigible_ballast_control, dirigible_control).chain().run_if(cinematic_inactive))
+        .add_systems(Update, (handle_balloon_boarding, animate_tethered_balloons))
+        .add_systems(Update, (update_time_of_day, time_of_day_controls.run_if(cinematic_inactive)))
+        .add_systems(Update, apply_lighting.after(update_time_of_day))
+        .add_systems(Update, (orbit_camera_input, rotate_camera).chain().run_if(cinematic_inactive))
+        .add_systems(Startup, load_camera_shots)
+        .add_systems(Update, (start_camera_shots, play_camera_shot).chain())
+        .add_systems(Update, (tag_protagonist_meshes, update_first_person_layers.run_if(cinematic_inactive)))
         .add_systems(Update, setup_scene_once_loaded.before(animate_targets))
-        .add_systems(Update, keyboard_animation_control)
+        .add_systems(Update, keyboard_animation_control.run_if(cinematic_inactive))
         .add_systems(Update, (
             sentry_follow_system,
             update_explosion_particles,
             update_explosion_light,
             periodic_sentry_spawn,
             animate_light_cones,
+            sentry_hear_noise,
+            sentry_notice_empty_tanks,
+            recover_stunned_sentries,
         ))
         .add_systems(Update, portal_system)      
         .add_systems(Update, handle_climbing)
         .add_systems(Update, handle_ladder_top)
         .add_systems(Update, check_ladder_presence.after(handle_climbing))
-        .add_systems(Update, climbing_keyboard_control)
+        .add_systems(Update, climbing_keyboard_control.run_if(cinematic_inactive))
         .add_systems(Update, swimming_system)
         .add_systems(Update, underwater_searchlight_system)
         .add_systems(Update, update_searchlight_rotation)
-        .add_systems(Update, teleport_system)
+        .add_systems(Update, teleport_system.run_if(cinematic_inactive))
         .add_systems(Update, (
             falling::check_falling,
             falling::handle_falling_animation,
+            falling::handle_landing.after(falling::check_falling),
         ))
         .add_systems(Startup, setup_debug_timer)
         .add_systems(Update, print_protagonist_transform)
@@ -121,32 +264,50 @@ fn main() {
             update_sentry_markers,
         ))
         .add_systems(Startup, setup_screenplay)
+        .add_systems(Startup, setup_interaction_prompt)
+        .add_systems(Update, (
+            find_interaction_focus,
+            update_interaction_prompt,
+            interaction_input.run_if(cinematic_inactive),
+        ).chain())
         .add_systems(Update, screenplay_system)
+        .add_systems(Startup, spawn_ice_particles)
         .add_systems(Update, update_ice_particles)
         .add_systems(Update, handle_ice_cave_interactions)
-        .add_systems(Update, handle_tank_interaction)
+        .add_systems(Update, (handle_tank_interaction, handle_tank_entry).chain())
         .add_systems(Update, handle_garage_approach)
         .add_systems(Update, check_dirigible_trigger)
         .add_systems(Update, handle_pipe_lift)
         .add_systems(Startup, spawn_lanterns)
-        .add_systems(Update, update_lanterns)
+        .add_systems(Update, (update_lanterns, update_lantern_lights))
+        .add_systems(Update, update_wind)
+        .add_systems(Startup, spawn_snowfall)
+        .add_systems(Update, (update_weather, apply_weather_fog, update_snowfall).chain())
+        .add_systems(Startup, spawn_tram_network)
+        .add_systems(Update, (move_trams, carry_tram_riders).chain())
+        .add_systems(Update, (handle_tram_interaction, update_tram_prompts))
+        .add_systems(Update, (apply_surface_friction, update_ground_surface, footstep_noise).chain())
+        .add_systems(Startup, setup_footprints)
+        .add_systems(Update, (
+            leave_footprints.after(update_ground_surface),
+            leave_tank_tracks,
+            fade_footprints,
+            sentries_follow_footprints,
+        ))
+        .add_systems(Startup, ((load_item_catalog, spawn_pickups).chain(), setup_inventory_hud))
+        .add_systems(Update, (
+            animate_pickups,
+            collect_pickups,
+            use_selected_item.run_if(cinematic_inactive),
+            update_flares,
+            update_noise_makers,
+            update_inventory_hud,
+        ))
+        .add_systems(Startup, setup_sabotage_hud)
+        .add_systems(Update, (plant_reactor_charge, update_reactor_sabotage).chain())
         .run();
 }
 
-fn animate_light_direction(
-    time: Res<Time>,
-    mut query: Query<&mut Transform, With<DirectionalLight>>,
-) {
-    for mut transform in &mut query {
-        transform.rotation = Quat::from_euler(
-            EulerRot::ZYX,
-            0.0,
-            time.elapsed_seconds() * PI / 5.0,
-            -FRAC_PI_4,
-        );
-    }
-}
-
 // Once the scene is loaded, start the animation
 fn setup_scene_once_loaded(
     mut commands: Commands,
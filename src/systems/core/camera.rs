@@ -1,21 +1,202 @@
-use bevy::prelude::*;
+use bevy::{
+    input::mouse::{MouseMotion, MouseWheel},
+    prelude::*,
+    render::view::RenderLayers,
+};
+use avian3d::prelude::*;
+use std::collections::HashMap;
 use crate::components::{Protagonist, HighAltitudeIndicator};
+use crate::systems::core::minimap::MinimapCamera;
+use crate::systems::player::dirigible::DirigibleBalloon;
+use crate::systems::player::falling::{impact_speed_for_drop, LETHAL_LANDING_SPEED};
+
+// How far down to look for the ground when checking for a lethal drop
+const DROP_CHECK_DISTANCE: f32 = 2000.0;
+
+// Orbit controls
+const ORBIT_MOUSE_SENSITIVITY: f32 = 0.005;  // Radians per pixel of mouse motion
+const ORBIT_STICK_SPEED: f32 = 2.5;  // Radians per second at full right-stick deflection
+const ORBIT_STICK_DEADZONE: f32 = 0.15;
+const ORBIT_MIN_PITCH: f32 = -0.35;  // Slightly below the protagonist
+const ORBIT_MAX_PITCH: f32 = 1.4;  // Nearly straight down
+const ZOOM_STEP: f32 = 0.1;
+const ZOOM_MIN: f32 = 0.3;
+const ZOOM_MAX: f32 = 3.0;
+
+// First-person view
+pub const PROTAGONIST_RENDER_LAYER: usize = 2;  // The protagonist's own meshes, hidden in first-person
+const FIRST_PERSON_EYE_HEIGHT: f32 = 2.0;  // Fallback when there is no head bone (e.g. in the tank)
+const FIRST_PERSON_EYE_FORWARD: f32 = 0.2;  // Nudge the eye in front of the face
+const HEAD_FOLLOW_SPEED: f32 = 30.0;
+
+// Marks the protagonist's head bone, found once the scene has loaded
+#[derive(Component)]
+pub struct HeadBone;
+
+// Spring arm collision
+const SPRING_ARM_PIVOT_HEIGHT: f32 = 1.5;  // Look-at point above the protagonist's origin
+const SPRING_ARM_MARGIN: f32 = 0.5;  // Keep the camera this far in front of any wall
+const CAMERA_FOLLOW_SPEED: f32 = 5.0;
+
+// Camera distance presets, one per movement state
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum CameraPreset {
+    OnFoot,
+    Climbing,
+    Driving,
+    Dirigible,
+    HighAltitude,
+}
+
+impl CameraPreset {
+    pub fn from_protagonist(protagonist: &Protagonist, position: Vec3) -> Self {
+        if protagonist.is_dirigible {
+            CameraPreset::Dirigible
+        } else if position.y > 100.0 {
+            CameraPreset::HighAltitude
+        } else if protagonist.is_driving && !protagonist.is_climbing {
+            CameraPreset::Driving
+        } else if !protagonist.is_driving && protagonist.is_climbing {
+            CameraPreset::Climbing
+        } else {
+            CameraPreset::OnFoot
+        }
+    }
+
+    // Follow offset behind the protagonist before orbit and zoom are applied
+    pub fn offset(self) -> Vec3 {
+        match self {
+            CameraPreset::OnFoot => Vec3::new(0.0, 2.0, 15.0),
+            CameraPreset::Climbing => Vec3::new(0.0, 2.0, 30.0),
+            CameraPreset::Driving => Vec3::new(0.0, 70.0, 300.0),  // Increased height and distance
+            CameraPreset::Dirigible => Vec3::new(0.0, 60.0, 200.0),  // Far enough back to see the balloon
+            CameraPreset::HighAltitude => Vec3::new(0.0, 30.0, 100.0),
+        }
+    }
+
+    // Elevation of the preset offset, which the orbit pitch is added to
+    pub fn pitch(self) -> f32 {
+        let offset = self.offset();
+        offset.y.atan2(offset.z)
+    }
+}
+
+// Range of orbit pitch the current view can actually show, so the stored offset never
+// winds past what is on screen
+fn orbit_pitch_limits(protagonist: &Protagonist, position: Vec3) -> (f32, f32) {
+    if protagonist.is_first_person {
+        (-ORBIT_MAX_PITCH, ORBIT_MAX_PITCH)
+    } else {
+        let preset_pitch = CameraPreset::from_protagonist(protagonist, position).pitch();
+        (ORBIT_MIN_PITCH - preset_pitch, ORBIT_MAX_PITCH - preset_pitch)
+    }
+}
+
+// Player-controlled orbit around the protagonist
+#[derive(Resource)]
+pub struct CameraOrbit {
+    pub yaw: f32,
+    pub pitch: f32,
+    pub zoom: HashMap<CameraPreset, f32>,  // Zoom is remembered per movement state
+}
+
+impl Default for CameraOrbit {
+    fn default() -> Self {
+        Self {
+            yaw: 0.0,
+            pitch: 0.0,
+            zoom: HashMap::new(),
+        }
+    }
+}
+
+impl CameraOrbit {
+    pub fn zoom_for(&self, preset: CameraPreset) -> f32 {
+        self.zoom.get(&preset).copied().unwrap_or(1.0)
+    }
+}
+
+// Orbit with the mouse (hold right button) or right stick, zoom with the wheel or right thumb buttons
+pub fn orbit_camera_input(
+    mut mouse_motion: EventReader<MouseMotion>,
+    mut mouse_wheel: EventReader<MouseWheel>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    gamepads: Res<Gamepads>,
+    gamepad_axes: Res<Axis<GamepadAxis>>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+    protagonist_query: Query<(&Transform, &Protagonist)>,
+    mut orbit: ResMut<CameraOrbit>,
+    time: Res<Time>,
+) {
+    let mut delta = Vec2::ZERO;
+    for motion in mouse_motion.read() {
+        if mouse_buttons.pressed(MouseButton::Right) {
+            delta += motion.delta * ORBIT_MOUSE_SENSITIVITY;
+        }
+    }
+
+    let mut zoom_steps = 0.0;
+    for wheel in mouse_wheel.read() {
+        zoom_steps -= wheel.y.signum();
+    }
+
+    for gamepad in gamepads.iter() {
+        let stick = Vec2::new(
+            gamepad_axes.get(GamepadAxis::new(gamepad, GamepadAxisType::RightStickX)).unwrap_or(0.0),
+            gamepad_axes.get(GamepadAxis::new(gamepad, GamepadAxisType::RightStickY)).unwrap_or(0.0),
+        );
+        if stick.length() > ORBIT_STICK_DEADZONE {
+            delta += Vec2::new(stick.x, -stick.y) * ORBIT_STICK_SPEED * time.delta_seconds();
+        }
+        if gamepad_buttons.just_pressed(GamepadButton::new(gamepad, GamepadButtonType::RightThumb)) {
+            zoom_steps -= 1.0;
+        }
+        if gamepad_buttons.just_pressed(GamepadButton::new(gamepad, GamepadButtonType::LeftThumb)) {
+            zoom_steps += 1.0;
+        }
+    }
+
+    let Ok((transform, protagonist)) = protagonist_query.get_single() else {
+        return;
+    };
+
+    // Re-clamped every frame so switching view or preset pulls the offset back in range
+    let (min_pitch, max_pitch) = orbit_pitch_limits(protagonist, transform.translation);
+    orbit.yaw -= delta.x;
+    orbit.pitch = (orbit.pitch + delta.y).clamp(min_pitch, max_pitch);
+
+    if zoom_steps != 0.0 {
+        let preset = CameraPreset::from_protagonist(protagonist, transform.translation);
+        let zoom = (orbit.zoom_for(preset) * (1.0 + zoom_steps * ZOOM_STEP)).clamp(ZOOM_MIN, ZOOM_MAX);
+        orbit.zoom.insert(preset, zoom);
+    }
+}
 
 pub fn rotate_camera(
     time: Res<Time>,
     protagonist_query: Query<(Entity, &Transform, &Protagonist, Option<&Children>)>,
-    mut camera_query: Query<(&mut Transform, &mut Projection), (With<Camera3d>, Without<Protagonist>)>,
+    mut camera_query: Query<(&mut Transform, &mut Projection), (With<Camera3d>, Without<Protagonist>, Without<MinimapCamera>)>,
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
     high_altitude_indicator_query: Query<Entity, With<HighAltitudeIndicator>>,
+    spatial_query: SpatialQuery,
+    orbit: Res<CameraOrbit>,
+    head_query: Query<&GlobalTransform, With<HeadBone>>,
 ) {
     if let Ok((protagonist_entity, protagonist_transform, protagonist, children)) = protagonist_query.get_single() {
         let protagonist_position = protagonist_transform.translation;
         let protagonist_rotation = protagonist_transform.rotation;
 
-        // Handle high altitude indicator
-        let is_high_altitude = protagonist_position.y > 100.0;
+        // Handle high altitude indicator - shown when a drop from here would be lethal
+        let drop_height = spatial_query.cast_ray(
+            protagonist_position,
+            Dir3::NEG_Y,
+            DROP_CHECK_DISTANCE,
+            true,
+            SpatialQueryFilter::from_excluded_entities([protagonist_entity]),
+        ).map_or(DROP_CHECK_DISTANCE, |hit| hit.time_of_impact);
+        let is_high_altitude = impact_speed_for_drop(drop_height) >= LETHAL_LANDING_SPEED;
         let has_indicator = high_altitude_indicator_query.iter().next().is_some();
 
         if is_high_altitude && 
@@ -40,51 +221,128 @@ pub fn rotate_camera(
                 HighAltitudeIndicator,
             )).set_parent(protagonist_entity);
         } else if (!is_high_altitude && has_indicator) || protagonist.is_dirigible {
-            // Remove indicator when below threshold
+            // Remove indicator when the drop is survivable
             for entity in high_altitude_indicator_query.iter() {
                 commands.entity(entity).despawn_recursive();
             }
         }
 
         for (mut camera_transform, mut projection) in camera_query.iter_mut() {
-            // Adjust follow offset based on state
-            let follow_offset = if protagonist.is_birds_eye {
-                Vec3::new(20.0, 150.0, 20.0) // Reduced height, added slight offset for depth perception
-            } else if protagonist_position.y > 100.0 {
-                Vec3::new(0.0, 30.0, 100.0)
-            } else if protagonist.is_driving && !protagonist.is_climbing {
-                Vec3::new(0.0, 70.0, 300.0)  // Driving - increased height and distance
-            } else if !protagonist.is_driving && protagonist.is_climbing {
-                Vec3::new(0.0, 2.0, 30.0)   // Climbing
-            } else {
-                Vec3::new(0.0, 2.0, 15.0)   // Default state
-            };
-
-            // Calculate the new camera position
-            let rotated_offset = if protagonist.is_birds_eye {
-                // Directly use the protagonist's rotation for birds-eye view
-                protagonist_rotation * follow_offset
-            } else if protagonist.is_driving {
-                let driving_rotation = Quat::from_rotation_y(std::f32::consts::FRAC_PI_2);
-                protagonist_rotation * driving_rotation * follow_offset
-            } else {
-                protagonist_rotation * follow_offset
-            };
-            let new_camera_position = protagonist_position + rotated_offset;
-
-            // Smoothly move the camera to the new position
-            camera_transform.translation = camera_transform
-                .translation
-                .lerp(new_camera_position, time.delta_seconds() * 5.0);
-
             // Look at logic for birds-eye view
             if protagonist.is_birds_eye {
+                // Reduced height, added slight offset for depth perception
+                let follow_offset = Vec3::new(20.0, 150.0, 20.0);
+                camera_transform.translation = camera_transform
+                    .translation
+                    .lerp(protagonist_position + protagonist_rotation * follow_offset, time.delta_seconds() * CAMERA_FOLLOW_SPEED);
+
                 // Look straight down while maintaining the protagonist's rotation
                 let up = protagonist_rotation * -Vec3::Z;
                 camera_transform.look_at(protagonist_position, up);
+                continue;
+            }
+
+            if protagonist.is_first_person {
+                let look_rotation = protagonist_rotation
+                    * Quat::from_rotation_y(orbit.yaw)
+                    * Quat::from_rotation_x(-orbit.pitch);
+
+                // Ride the animated head bone so the view bobs with the locomotion animation
+                let eye_position = head_query
+                    .get_single()
+                    .map(|head| head.translation())
+                    .unwrap_or(protagonist_position + Vec3::Y * FIRST_PERSON_EYE_HEIGHT)
+                    + look_rotation * Vec3::NEG_Z * FIRST_PERSON_EYE_FORWARD;
+
+                camera_transform.translation = camera_transform
+                    .translation
+                    .lerp(eye_position, (time.delta_seconds() * HEAD_FOLLOW_SPEED).min(1.0));
+                camera_transform.rotation = look_rotation;
+                continue;
+            }
+
+            // Convert the state's preset offset into an arm length and elevation, then apply the orbit
+            let preset = CameraPreset::from_protagonist(protagonist, protagonist_position);
+            let preset_offset = preset.offset();
+            let arm_length = preset_offset.length() * orbit.zoom_for(preset);
+            let pitch = (preset.pitch() + orbit.pitch).clamp(ORBIT_MIN_PITCH, ORBIT_MAX_PITCH);
+
+            let heading = if protagonist.is_driving {
+                protagonist_rotation * Quat::from_rotation_y(std::f32::consts::FRAC_PI_2)
+            } else {
+                protagonist_rotation
+            };
+            let arm_direction = heading * Quat::from_rotation_y(orbit.yaw) * Quat::from_rotation_x(-pitch) * Vec3::Z;
+
+            // Spring arm: pull the camera in when geometry blocks the view
+            let pivot = protagonist_position + Vec3::Y * SPRING_ARM_PIVOT_HEIGHT;
+            let blocked_length = Dir3::new(arm_direction).ok().and_then(|direction| {
+                spatial_query.cast_ray(
+                    pivot,
+                    direction,
+                    arm_length,
+                    true,
+                    SpatialQueryFilter::from_excluded_entities([protagonist_entity]),
+                )
+            }).map(|hit| (hit.time_of_impact - SPRING_ARM_MARGIN).max(0.0));
+
+            if let Some(length) = blocked_length {
+                // Snap in immediately so the view never clips through the wall
+                camera_transform.translation = pivot + arm_direction * length;
             } else {
-                camera_transform.look_at(protagonist_position, Vec3::Y);
+                // Smoothly move the camera back out to the full arm length
+                camera_transform.translation = camera_transform
+                    .translation
+                    .lerp(pivot + arm_direction * arm_length, time.delta_seconds() * CAMERA_FOLLOW_SPEED);
+            }
+
+            camera_transform.look_at(pivot, Vec3::Y);
+        }
+    }
+}
+
+// Move the protagonist's meshes onto their own render layer and find the head bone.
+// Runs every frame because entering or leaving the tank swaps the protagonist's scene.
+pub fn tag_protagonist_meshes(
+    mut commands: Commands,
+    protagonist_query: Query<Entity, With<Protagonist>>,
+    children_query: Query<&Children>,
+    mesh_query: Query<(), (With<Handle<Mesh>>, Without<RenderLayers>, Without<DirigibleBalloon>, Without<HighAltitudeIndicator>)>,
+    name_query: Query<&Name, Without<HeadBone>>,
+) {
+    let Ok(protagonist_entity) = protagonist_query.get_single() else {
+        return;
+    };
+
+    for descendant in children_query.iter_descendants(protagonist_entity) {
+        if mesh_query.contains(descendant) {
+            commands.entity(descendant).insert(RenderLayers::layer(PROTAGONIST_RENDER_LAYER));
+        }
+        if let Ok(name) = name_query.get(descendant) {
+            if name.as_str().ends_with("Head") {
+                commands.entity(descendant).insert(HeadBone);
             }
         }
     }
-}
\ No newline at end of file
+}
+
+// Layers the main camera renders: everything but the protagonist's own meshes in first-person
+pub fn follow_camera_layers(is_first_person: bool) -> RenderLayers {
+    if is_first_person {
+        RenderLayers::layer(0)
+    } else {
+        RenderLayers::from_layers(&[0, PROTAGONIST_RENDER_LAYER])
+    }
+}
+
+// Hide the protagonist's own meshes from the main camera in first-person
+pub fn update_first_person_layers(
+    protagonist_query: Query<&Protagonist, Changed<Protagonist>>,
+    mut camera_query: Query<&mut RenderLayers, (With<Camera3d>, Without<MinimapCamera>)>,
+) {
+    if let Ok(protagonist) = protagonist_query.get_single() {
+        for mut layers in camera_query.iter_mut() {
+            *layers = follow_camera_layers(protagonist.is_first_person);
+        }
+    }
+}
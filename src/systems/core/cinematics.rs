@@ -0,0 +1,338 @@
+use bevy::{
+    prelude::*,
+    render::view::RenderLayers,
+};
+use avian3d::prelude::*;
+use std::f32::consts::FRAC_PI_4;
+use crate::components::Protagonist;
+use crate::systems::core::camera::follow_camera_layers;
+use crate::systems::core::data_file::read_data_file;
+use crate::systems::core::minimap::MinimapCamera;
+
+const DEFAULT_FOV: f32 = FRAC_PI_4;  // Bevy's default perspective FOV, restored after a shot
+
+// Camera rails live here so designers can add shots without code changes. Relative to the assets folder.
+const CAMERA_SHOT_DATA_PATH: &str = "data/camera_shots.txt";
+
+#[derive(Clone, Copy, Debug)]
+pub enum Easing {
+    Linear,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+}
+
+impl Easing {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "linear" => Some(Easing::Linear),
+            "ease_in" => Some(Easing::EaseIn),
+            "ease_out" => Some(Easing::EaseOut),
+            "ease_in_out" => Some(Easing::EaseInOut),
+            _ => None,
+        }
+    }
+
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t,
+            Easing::EaseOut => 1.0 - (1.0 - t) * (1.0 - t),
+            Easing::EaseInOut => t * t * (3.0 - 2.0 * t),
+        }
+    }
+}
+
+// One point on a camera rail. Positions are relative to the shot's anchor.
+// `easing` shapes the move from the previous keyframe into this one.
+#[derive(Clone, Copy, Debug)]
+pub struct CameraKeyframe {
+    pub time: f32,
+    pub position: Vec3,
+    pub look_at: Vec3,
+    pub fov: f32,  // Degrees
+    pub easing: Easing,
+}
+
+#[derive(Clone, Debug)]
+pub struct CameraShot {
+    pub name: String,
+    pub keyframes: Vec<CameraKeyframe>,
+}
+
+#[derive(Resource, Default)]
+pub struct CameraShots {
+    pub shots: Vec<CameraShot>,
+}
+
+impl CameraShots {
+    pub fn get(&self, name: &str) -> Option<&CameraShot> {
+        self.shots.iter().find(|shot| shot.name == name)
+    }
+
+    // Parse the `|` separated shot format, skipping (and logging) bad lines and empty shots
+    fn parse(source: &str) -> Self {
+        let mut shots: Vec<CameraShot> = Vec::new();
+
+        for (line_number, line) in source.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let fields: Vec<&str> = line.split('|').map(str::trim).collect();
+            let parsed = match fields.as_slice() {
+                ["shot", name] => {
+                    shots.push(CameraShot { name: name.to_string(), keyframes: Vec::new() });
+                    Some(())
+                }
+                ["key", time, position, look_at, fov, easing] => shots
+                    .last_mut()
+                    .zip(parse_keyframe(time, position, look_at, fov, easing))
+                    .filter(|(shot, keyframe)| shot.keyframes.last().is_none_or(|last| keyframe.time > last.time))
+                    .map(|(shot, keyframe)| shot.keyframes.push(keyframe)),
+                _ => None,
+            };
+
+            if parsed.is_none() {
+                warn!("{}:{}: could not parse '{}'", CAMERA_SHOT_DATA_PATH, line_number + 1, line);
+            }
+        }
+
+        shots.retain(|shot| {
+            if shot.keyframes.is_empty() {
+                warn!("{}: camera shot '{}' has no keyframes", CAMERA_SHOT_DATA_PATH, shot.name);
+            }
+            !shot.keyframes.is_empty()
+        });
+        Self { shots }
+    }
+}
+
+fn parse_vec3(value: &str) -> Option<Vec3> {
+    let values: Vec<f32> = value
+        .split(',')
+        .map(|part| part.trim().parse().ok())
+        .collect::<Option<_>>()?;
+    match values.as_slice() {
+        [x, y, z] => Some(Vec3::new(*x, *y, *z)),
+        _ => None,
+    }
+}
+
+fn parse_keyframe(time: &str, position: &str, look_at: &str, fov: &str, easing: &str) -> Option<CameraKeyframe> {
+    Some(CameraKeyframe {
+        time: time.parse().ok()?,
+        position: parse_vec3(position)?,
+        look_at: parse_vec3(look_at)?,
+        fov: fov.parse().ok()?,
+        easing: Easing::parse(easing)?,
+    })
+}
+
+pub fn load_camera_shots(mut commands: Commands) {
+    let shots = read_data_file(CAMERA_SHOT_DATA_PATH)
+        .map(|source| CameraShots::parse(&source))
+        .unwrap_or_default();
+
+    info!("Loaded {} camera shots", shots.shots.len());
+    commands.insert_resource(shots);
+}
+
+// Sent by the screenplay or triggers to play a shot from `CameraShots`
+#[derive(Event)]
+pub struct PlayCameraShot {
+    pub name: &'static str,
+    pub anchor: Vec3,
+}
+
+pub struct ActiveShot {
+    keyframes: Vec<CameraKeyframe>,
+    anchor: Vec3,
+    elapsed: f32,
+}
+
+#[derive(Resource, Default)]
+pub struct CinematicState {
+    pub active: Option<ActiveShot>,
+    pub played: Vec<String>,
+}
+
+impl CinematicState {
+    pub fn is_playing(&self) -> bool {
+        self.active.is_some()
+    }
+}
+
+#[derive(Component)]
+pub struct CinematicSkipHint;
+
+// Run condition for player input and the follow camera
+pub fn cinematic_inactive(state: Res<CinematicState>) -> bool {
+    !state.is_playing()
+}
+
+// Sample a rail at `time`, returning (position, look_at, fov in degrees) relative to the anchor
+fn sample_keyframes(keyframes: &[CameraKeyframe], time: f32) -> (Vec3, Vec3, f32) {
+    let first = keyframes[0];
+    if time <= first.time {
+        return (first.position, first.look_at, first.fov);
+    }
+
+    for window in keyframes.windows(2) {
+        let (from, to) = (window[0], window[1]);
+        if time <= to.time {
+            let t = to.easing.apply((time - from.time) / (to.time - from.time).max(f32::EPSILON));
+            return (
+                from.position.lerp(to.position, t),
+                from.look_at.lerp(to.look_at, t),
+                from.fov + (to.fov - from.fov) * t,
+            );
+        }
+    }
+
+    let last = keyframes[keyframes.len() - 1];
+    (last.position, last.look_at, last.fov)
+}
+
+pub fn start_camera_shots(
+    mut commands: Commands,
+    mut shot_events: EventReader<PlayCameraShot>,
+    mut state: ResMut<CinematicState>,
+    camera_shots: Res<CameraShots>,
+    mut velocity_query: Query<&mut LinearVelocity, With<Protagonist>>,
+    mut camera_query: Query<&mut RenderLayers, (With<Camera3d>, Without<MinimapCamera>)>,
+) {
+    for shot in shot_events.read() {
+        if state.is_playing() || state.played.iter().any(|name| name == shot.name) {
+            continue;
+        }
+
+        let Some(camera_shot) = camera_shots.get(shot.name) else {
+            warn!("Unknown camera shot: {}", shot.name);
+            continue;
+        };
+
+        info!("Playing camera shot: {}", shot.name);
+        state.played.push(shot.name.to_string());
+        state.active = Some(ActiveShot {
+            keyframes: camera_shot.keyframes.clone(),
+            anchor: shot.anchor,
+            elapsed: 0.0,
+        });
+
+        // Stop the protagonist where they are while input is suspended
+        for mut velocity in velocity_query.iter_mut() {
+            velocity.0.x = 0.0;
+            velocity.0.z = 0.0;
+        }
+
+        // Show the protagonist even if we were in first-person
+        for mut layers in camera_query.iter_mut() {
+            *layers = follow_camera_layers(false);
+        }
+
+        commands.spawn((
+            TextBundle::from_section(
+                "ESC TO SKIP",
+                TextStyle {
+                    font_size: 20.0,
+                    color: Color::srgba(1.0, 1.0, 1.0, 0.6),
+                    ..default()
+                },
+            )
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(20.0),
+                right: Val::Px(20.0),
+                ..default()
+            }),
+            CinematicSkipHint,
+        ));
+    }
+}
+
+pub fn play_camera_shot(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+    mut state: ResMut<CinematicState>,
+    mut camera_query: Query<(&mut Transform, &mut Projection, &mut RenderLayers), (With<Camera3d>, Without<MinimapCamera>)>,
+    protagonist_query: Query<&Protagonist>,
+    hint_query: Query<Entity, With<CinematicSkipHint>>,
+    time: Res<Time>,
+) {
+    let Some(shot) = state.active.as_mut() else {
+        return;
+    };
+
+    shot.elapsed += time.delta_seconds();
+
+    let skipped = keyboard_input.just_pressed(KeyCode::Escape)
+        || gamepads.iter().any(|gamepad| {
+            gamepad_buttons.just_pressed(GamepadButton::new(gamepad, GamepadButtonType::Start))
+        });
+    let finished = shot.elapsed >= shot.keyframes[shot.keyframes.len() - 1].time;
+
+    if skipped || finished {
+        state.active = None;
+        let is_first_person = protagonist_query.get_single().is_ok_and(|protagonist| protagonist.is_first_person);
+        for (_, mut projection, mut layers) in camera_query.iter_mut() {
+            if let Projection::Perspective(perspective) = projection.as_mut() {
+                perspective.fov = DEFAULT_FOV;
+            }
+            // Hide the protagonist again if the shot interrupted first-person
+            *layers = follow_camera_layers(is_first_person);
+        }
+        for hint in hint_query.iter() {
+            commands.entity(hint).despawn_recursive();
+        }
+        return;
+    }
+
+    let (position, look_at, fov) = sample_keyframes(&shot.keyframes, shot.elapsed);
+    for (mut camera_transform, mut projection, _) in camera_query.iter_mut() {
+        camera_transform.translation = shot.anchor + position;
+        camera_transform.look_at(shot.anchor + look_at, Vec3::Y);
+        if let Projection::Perspective(perspective) = projection.as_mut() {
+            perspective.fov = fov.to_radians();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_shots_and_skips_bad_keyframes() {
+        let shots = CameraShots::parse(
+            "key | 0.0 | 0,0,0 | 0,0,0 | 45 | linear\n\
+             shot | flyover\n\
+             key | 0.0 | 0,10,0 | 0,0,0 | 45 | linear\n\
+             key | 2.0 | 10,10,0 | 0,0,0 | 60 | ease_in_out\n\
+             key | 1.0 | 5,10,0 | 0,0,0 | 50 | linear\n\
+             key | 3.0 | 5,10,0 | 0,0,0 | 50 | wobble\n\
+             shot | empty\n",
+        );
+
+        assert_eq!(shots.shots.len(), 1);
+        let flyover = shots.get("flyover").unwrap();
+        assert_eq!(flyover.keyframes.len(), 2);
+        assert_eq!(flyover.keyframes[1].position, Vec3::new(10.0, 10.0, 0.0));
+
+        let (position, _, fov) = sample_keyframes(&flyover.keyframes, 1.0);
+        assert_eq!(position, Vec3::new(5.0, 10.0, 0.0));
+        assert_eq!(fov, 52.5);
+    }
+
+    #[test]
+    fn shipped_shots_parse() {
+        let shots = CameraShots::parse(&read_data_file(CAMERA_SHOT_DATA_PATH).unwrap());
+        for name in ["level_complete_flyover", "reactor_reveal", "dirigible_liftoff"] {
+            assert!(shots.get(name).is_some(), "missing camera shot {}", name);
+        }
+    }
+}
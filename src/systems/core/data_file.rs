@@ -0,0 +1,27 @@
+use bevy::{
+    asset::io::file::FileAssetReader,
+    prelude::*,
+};
+use std::fs;
+use std::path::PathBuf;
+
+// Folder the asset server reads from, relative to the asset root
+const ASSET_FOLDER: &str = "assets";
+
+// Resolve an asset-relative path the same way the asset server does, so data files are found
+// whatever directory the game is launched from (BEVY_ASSET_ROOT, the cargo manifest or the executable)
+pub fn data_file_path(path: &str) -> PathBuf {
+    FileAssetReader::get_base_path().join(ASSET_FOLDER).join(path)
+}
+
+// Read a text data file at startup, logging and returning None if it is missing
+pub fn read_data_file(path: &str) -> Option<String> {
+    let full_path = data_file_path(path);
+    match fs::read_to_string(&full_path) {
+        Ok(source) => Some(source),
+        Err(error) => {
+            warn!("Could not read {}: {}", full_path.display(), error);
+            None
+        }
+    }
+}
@@ -0,0 +1,143 @@
+use bevy::prelude::*;
+use crate::components::Protagonist;
+
+// How far off the protagonist's facing an interactable can be and still get picked (cosine)
+const INTERACT_MIN_FACING: f32 = -0.2;
+// Inside this horizontal distance facing doesn't matter (e.g. standing in a trigger)
+const INTERACT_CLOSE_RANGE: f32 = 5.0;
+// E is taken by strafing and side-stepping while climbing
+const INTERACT_KEY: KeyCode = KeyCode::Enter;
+
+// Something the protagonist can use with Enter. The owning module reacts to `InteractEvent`s
+// targeting its entity.
+#[derive(Component)]
+pub struct Interactable {
+    pub prompt: String,  // Shown as "Press ENTER to {prompt}"
+    pub range: f32,
+}
+
+impl Interactable {
+    pub fn new(prompt: impl Into<String>, range: f32) -> Self {
+        Self {
+            prompt: prompt.into(),
+            range,
+        }
+    }
+}
+
+#[derive(Event)]
+pub struct InteractEvent {
+    pub target: Entity,
+}
+
+// The interactable currently shown in the prompt
+#[derive(Resource, Default)]
+pub struct InteractionFocus {
+    pub target: Option<Entity>,
+}
+
+#[derive(Component)]
+pub struct InteractionPromptText;
+
+pub fn setup_interaction_prompt(mut commands: Commands) {
+    commands.spawn((
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font_size: 26.0,
+                color: Color::WHITE,
+                ..default()
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            bottom: Val::Percent(30.0),
+            left: Val::Percent(45.0),
+            ..default()
+        }),
+        InteractionPromptText,
+    ));
+}
+
+// Pick the closest interactable in range that the protagonist is roughly facing
+pub fn find_interaction_focus(
+    mut focus: ResMut<InteractionFocus>,
+    protagonist_query: Query<&Transform, With<Protagonist>>,
+    interactable_query: Query<(Entity, &GlobalTransform, &Interactable)>,
+) {
+    let Ok(protagonist_transform) = protagonist_query.get_single() else {
+        focus.target = None;
+        return;
+    };
+
+    let position = protagonist_transform.translation;
+    let forward = protagonist_transform.forward().as_vec3().with_y(0.0).normalize_or_zero();
+
+    let mut best: Option<(Entity, f32)> = None;
+    for (entity, interactable_transform, interactable) in interactable_query.iter() {
+        let offset = interactable_transform.translation() - position;
+        let distance = offset.length();
+        if distance > interactable.range {
+            continue;
+        }
+
+        let horizontal = offset.with_y(0.0);
+        let facing = if horizontal.length() < INTERACT_CLOSE_RANGE {
+            1.0
+        } else {
+            forward.dot(horizontal.normalize())
+        };
+        if facing < INTERACT_MIN_FACING {
+            continue;
+        }
+
+        // Prefer things that are close relative to their range and straight ahead
+        let score = distance / interactable.range + (1.0 - facing);
+        if best.map_or(true, |(_, best_score)| score < best_score) {
+            best = Some((entity, score));
+        }
+    }
+
+    focus.target = best.map(|(entity, _)| entity);
+}
+
+pub fn update_interaction_prompt(
+    focus: Res<InteractionFocus>,
+    interactable_query: Query<&Interactable>,
+    mut text_query: Query<&mut Text, With<InteractionPromptText>>,
+) {
+    let Ok(mut text) = text_query.get_single_mut() else {
+        return;
+    };
+
+    let prompt = focus
+        .target
+        .and_then(|target| interactable_query.get(target).ok())
+        .map(|interactable| format!("Press ENTER to {}", interactable.prompt))
+        .unwrap_or_default();
+
+    if text.sections[0].value != prompt {
+        text.sections[0].value = prompt;
+    }
+}
+
+pub fn interaction_input(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+    focus: Res<InteractionFocus>,
+    mut interact_events: EventWriter<InteractEvent>,
+) {
+    let Some(target) = focus.target else {
+        return;
+    };
+
+    let pressed = keyboard_input.just_pressed(INTERACT_KEY)
+        || gamepads.iter().any(|gamepad| {
+            gamepad_buttons.just_pressed(GamepadButton::new(gamepad, GamepadButtonType::West))
+        });
+
+    if pressed {
+        interact_events.send(InteractEvent { target });
+    }
+}
@@ -1,5 +1,6 @@
 use crate::components::Protagonist;
 use crate::resources::{ProtagonistAnimations, PROTAGONIST_ANIMATIONS};
+use crate::systems::player::falling::FallTracker;
 
 
 use bevy::{
@@ -15,6 +16,7 @@ const TURN_SPEED: f32 = 3.0;  // Radians per second
 const MOVE_SPEED: f32 = 20.0;  // Base units per second
 const RUN_SPEED: f32 = 80.0;  // Base running speed
 const STRAFE_SPEED: f32 = 4.0;  // Base strafing speed
+const CRAWL_SPEED: f32 = 6.0;  // Base crawling speed
 const UNDERWATER_SPEED: f32 = 80.0;  // Underwater movement speed
 
 // Height-related constants
@@ -41,24 +43,17 @@ const JUMP_VERTICAL_RUNNING: f32 = 8.0;
 // Animation transition duration
 const ANIMATION_TRANSITION_MS: u64 = 250;
 
-// Lighting values
-const NIGHT_ILLUMINANCE: f32 = 10.0;
-const ALARM_ILLUMINANCE: f32 = 1000.0;
-const NIGHT_COLOR: Color = Color::rgb(0.2, 0.2, 0.3);
-const ALARM_COLOR: Color = Color::rgb(1.0, 0.0, 0.0);
-
 pub fn keyboard_animation_control(
     keyboard_input: Res<ButtonInput<KeyCode>>,
     mut impulse_query: Query<&mut ExternalImpulse, With<Protagonist>>,
-    mut protagonist_query: Query<(&mut Transform, &mut Protagonist)>,
+    mut protagonist_query: Query<(&mut Transform, &mut Protagonist, &FallTracker)>,
     mut velocity_query: Query<&mut LinearVelocity, With<Protagonist>>,
     mut angular_velocity_query: Query<&mut AngularVelocity, With<Protagonist>>,
-    mut directional_light_query: Query<&mut DirectionalLight>,
     mut animation_players: Query<(&mut AnimationPlayer, &mut AnimationTransitions)>,
     animations: Res<ProtagonistAnimations>,
 ) {
 
-    if let Ok((mut protagonist_transform, mut protagonist)) = protagonist_query.get_single_mut() {
+    if let Ok((mut protagonist_transform, mut protagonist, fall_tracker)) = protagonist_query.get_single_mut() {
         // Manual up/down teleportation
         if keyboard_input.just_pressed(KeyCode::KeyV) {
             protagonist_transform.translation.y -= TELEPORT_DOWN_DISTANCE;
@@ -76,10 +71,11 @@ pub fn keyboard_animation_control(
             HEIGHT_MULTIPLIER_HIGH 
         } else { 
             HEIGHT_MULTIPLIER_NORMAL 
-        };
+        } * fall_tracker.speed_multiplier();  // Slowed while recovering from a hard landing
         let adjusted_move_speed = MOVE_SPEED * height_multiplier;
         let adjusted_run_speed = RUN_SPEED * height_multiplier;
         let adjusted_strafe_speed = STRAFE_SPEED * height_multiplier;
+        let adjusted_crawl_speed = CRAWL_SPEED * height_multiplier;
 
         // Extract only Y rotation and force upright orientation
         let (yaw, _, _) = protagonist_transform.rotation.to_euler(EulerRot::YXZ);
@@ -298,8 +294,13 @@ pub fn keyboard_animation_control(
                     }
                 }
 
+                // Hold Ctrl to crawl
+                let crawling = keyboard_input.pressed(KeyCode::ControlLeft);
+
                 if keyboard_input.pressed(KeyCode::KeyW) {
-                    if let Some(run) = PROTAGONIST_ANIMATIONS.get("ADVANCE") {
+                    protagonist.is_crawling = crawling;
+                    let advance_anim = if crawling { "CRAWL" } else { "ADVANCE" };
+                    if let Some(run) = PROTAGONIST_ANIMATIONS.get(advance_anim) {
                         if !player.is_playing_animation(animations.animations[*run]) {
                             let animation = transitions
                                 .play(
@@ -310,11 +311,13 @@ pub fn keyboard_animation_control(
                                 .set_repeat(RepeatAnimation::Forever);
                             
                             // Set animation speed based on movement speed
-                            if keyboard_input.pressed(KeyCode::ShiftLeft) {
+                            if keyboard_input.pressed(KeyCode::ShiftLeft) && !crawling {
                                 animation.set_speed(2.0);
                             }
                         }
-                        let movement_speed = if keyboard_input.pressed(KeyCode::ShiftLeft) {
+                        let movement_speed = if crawling {
+                            adjusted_crawl_speed
+                        } else if keyboard_input.pressed(KeyCode::ShiftLeft) {
                             adjusted_run_speed
                         } else {
                             adjusted_move_speed
@@ -325,7 +328,9 @@ pub fn keyboard_animation_control(
                         }
                     }
                 } else if keyboard_input.pressed(KeyCode::KeyS) {
-                    if let Some(walk_backward) = PROTAGONIST_ANIMATIONS.get("WALK_BACK") {
+                    protagonist.is_crawling = crawling;
+                    let backward_anim = if crawling { "CRAWL_BACKWARDS" } else { "WALK_BACK" };
+                    if let Some(walk_backward) = PROTAGONIST_ANIMATIONS.get(backward_anim) {
                         if !player.is_playing_animation(animations.animations[*walk_backward]) {
                             let animation = transitions
                                 .play(
@@ -336,11 +341,13 @@ pub fn keyboard_animation_control(
                                 .set_repeat(RepeatAnimation::Forever);
                             
                             // Set animation speed based on movement speed
-                            if keyboard_input.pressed(KeyCode::ShiftLeft) {
+                            if keyboard_input.pressed(KeyCode::ShiftLeft) && !crawling {
                                 animation.set_speed(2.0);
                             }
                         }
-                        let movement_speed = if keyboard_input.pressed(KeyCode::ShiftLeft) {
+                        let movement_speed = if crawling {
+                            adjusted_crawl_speed
+                        } else if keyboard_input.pressed(KeyCode::ShiftLeft) {
                             adjusted_run_speed
                         } else {
                             adjusted_move_speed
@@ -350,6 +357,7 @@ pub fn keyboard_animation_control(
                         }
                     }
                 } else if !keyboard_input.pressed(KeyCode::KeyW) && !keyboard_input.pressed(KeyCode::KeyS) {
+                    protagonist.is_crawling = false;
                     if let Some(idle) = PROTAGONIST_ANIMATIONS.get("CROUCH") {
                         if !player.is_playing_animation(animations.animations[*idle]) {
                             transitions
@@ -440,33 +448,24 @@ pub fn keyboard_animation_control(
                 }
             }
 
-            // Handle other special actions
-            if keyboard_input.just_pressed(KeyCode::KeyC) {
-                info!("C key pressed");
-                // ... rest of charge placement code ...
-            }
-
-            // Toggle lighting with K for night and L for alarm
-            if keyboard_input.just_pressed(KeyCode::KeyK) {
-                for mut light in directional_light_query.iter_mut() {
-                    // Switch to dark night mode
-                    light.illuminance = NIGHT_ILLUMINANCE;
-                    light.color = NIGHT_COLOR;
-                }
-            }
-
-            if keyboard_input.just_pressed(KeyCode::KeyL) {
-                for mut light in directional_light_query.iter_mut() {
-                    // Switch to red alarm lights
-                    light.illuminance = ALARM_ILLUMINANCE;
-                    light.color = ALARM_COLOR;
-                }
-            }
-
             // Replace the Tab animation cycling with camera toggle
+            // Cycles Normal -> Birds-eye -> First-person -> Normal
             if keyboard_input.just_pressed(KeyCode::Tab) {
-                protagonist.is_birds_eye = !protagonist.is_birds_eye;
-                info!("Camera view: {}", if protagonist.is_birds_eye { "Birds-eye" } else { "Normal" });
+                if protagonist.is_birds_eye {
+                    protagonist.is_birds_eye = false;
+                    protagonist.is_first_person = true;
+                } else if protagonist.is_first_person {
+                    protagonist.is_first_person = false;
+                } else {
+                    protagonist.is_birds_eye = true;
+                }
+                info!("Camera view: {}", if protagonist.is_birds_eye {
+                    "Birds-eye"
+                } else if protagonist.is_first_person {
+                    "First-person"
+                } else {
+                    "Normal"
+                });
             }
         }        
     }
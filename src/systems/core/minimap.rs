@@ -5,6 +5,8 @@ use bevy::{
 };
 use crate::components::Protagonist;
 use crate::components::Sentry;
+use crate::systems::core::sentry::SentryStunned;
+use crate::systems::environments::weather::Weather;
 
 // Add marker component for minimap elements
 #[derive(Component)]
@@ -29,6 +31,7 @@ pub const MINIMAP_MARKER_SIZE: f32 = 3.0;
 pub struct MinimapResources {
     pub sentry_mesh: Handle<Mesh>,
     pub sentry_material: Handle<StandardMaterial>,
+    pub stunned_sentry_material: Handle<StandardMaterial>,
 }
 
 pub fn setup_minimap(
@@ -85,6 +88,11 @@ pub fn setup_minimap(
             emissive: Color::srgba(1.0, 0.6, 0.0, 0.5).into(),
             ..default()
         }),
+        stunned_sentry_material: materials.add(StandardMaterial {
+            base_color: Color::srgb(0.3, 0.6, 1.0), // Pale blue while knocked out
+            emissive: Color::srgba(0.3, 0.6, 1.0, 0.5).into(),
+            ..default()
+        }),
     });
 }
 
@@ -95,7 +103,7 @@ pub fn update_minimap(
         Query<&mut Transform, (With<MinimapCamera>, Without<Protagonist>, Without<MinimapMarker>)>,
         Query<&mut Transform, (With<MinimapMarker>, Without<MinimapCamera>, Without<Protagonist>)>,
         Query<&mut Camera, With<MinimapCamera>>,
-        Query<&mut Visibility, (With<MinimapMarker>, Without<Protagonist>)>,
+        Query<&mut Visibility, (With<MinimapMarker>, Without<Protagonist>, Without<SentryMinimapMarker>)>,
     )>,
     time: Res<Time>,
 ) {
@@ -109,7 +117,7 @@ pub fn update_minimap(
             });
         }
 
-        // Always show markers
+        // Always show the protagonist's marker; sentry markers depend on the weather
         for mut visibility in param_set.p3().iter_mut() {
             *visibility = Visibility::Visible;
         }
@@ -144,23 +152,47 @@ pub fn update_minimap(
     }
 }
 
-// Add system to update sentry markers
+// Add system to update sentry markers. Sentries too far off to make out in the
+// weather drop off the minimap.
 pub fn update_sentry_markers(
     mut param_set: ParamSet<(
-        Query<(&Transform, Entity), With<Sentry>>,
-        Query<(&mut Transform, &SentryMinimapMarker)>,
+        Query<(&Transform, Entity, Has<SentryStunned>), With<Sentry>>,
+        Query<(&mut Transform, &mut Handle<StandardMaterial>, &mut Visibility, &SentryMinimapMarker)>,
+        Query<&Transform, With<Protagonist>>,
     )>,
+    minimap_resources: Res<MinimapResources>,
+    weather: Res<Weather>,
     time: Res<Time>,
 ) {
+    let Some(protagonist_pos) = param_set.p2().get_single().ok().map(|transform| transform.translation) else {
+        return;
+    };
+    let minimap_range = weather.minimap_range();
+
     // Collect sentry positions first
-    let sentry_positions: Vec<(Entity, Vec3)> = param_set.p0()
+    let sentry_positions: Vec<(Entity, Vec3, bool)> = param_set.p0()
         .iter()
-        .map(|(transform, entity)| (entity, transform.translation))
+        .map(|(transform, entity, stunned)| (entity, transform.translation, stunned))
         .collect();
 
     // Update marker positions
-    for (mut marker_transform, marker) in param_set.p1().iter_mut() {
-        if let Some((_, sentry_pos)) = sentry_positions.iter().find(|(entity, _)| *entity == marker.0) {
+    for (mut marker_transform, mut material, mut visibility, marker) in param_set.p1().iter_mut() {
+        if let Some((_, sentry_pos, stunned)) = sentry_positions.iter().find(|(entity, _, _)| *entity == marker.0) {
+            let visible = Vec2::new(sentry_pos.x - protagonist_pos.x, sentry_pos.z - protagonist_pos.z).length() < minimap_range;
+            let wanted = if visible { Visibility::Visible } else { Visibility::Hidden };
+            if *visibility != wanted {
+                *visibility = wanted;
+            }
+
+            let marker_material = if *stunned {
+                &minimap_resources.stunned_sentry_material
+            } else {
+                &minimap_resources.sentry_material
+            };
+            if *material != *marker_material {
+                *material = marker_material.clone();
+            }
+
             let target_pos = Vec3::new(
                 sentry_pos.x,
                 MINIMAP_MARKER_HEIGHT,
@@ -4,4 +4,7 @@ pub mod camera;
 pub mod minimap;
 pub mod sentry;
 pub mod screenplay;
-pub mod keyboard_input;
\ No newline at end of file
+pub mod keyboard_input;
+pub mod cinematics;
+pub mod interaction;
+pub mod data_file;
\ No newline at end of file
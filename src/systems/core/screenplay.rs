@@ -16,6 +16,7 @@ use crate::systems::player::teleports::{
 };
 
 use crate::components::Protagonist;
+use crate::systems::core::cinematics::PlayCameraShot;
 
 const TEXT_DISPLAY_DURATION: f32 = 5.0; // seconds
 const TRIGGER_DISTANCE: f32 = 20.0; // units
@@ -82,6 +83,12 @@ const SEQUENCES: &[(&str, &[(&str, Color)])] = &[
     ]),
 ];
 
+// Camera shots played when a sequence starts, anchored at the sequence's trigger position
+const SEQUENCE_SHOTS: &[(&str, &str, Vec3)] = &[
+    ("level_complete", "level_complete_flyover", LEVEL_COMPLETE_POSITION),
+    ("reactor", "reactor_reveal", REACTOR),
+];
+
 #[derive(Component)]
 pub struct ScreenplayText;
 
@@ -170,6 +177,7 @@ pub fn screenplay_system(
     mut message_state: ResMut<MessageState>,
     mut text_query: Query<&mut Text, With<ScreenplayText>>,
     protagonist_query: Query<&Transform, With<Protagonist>>,
+    mut camera_shots: EventWriter<PlayCameraShot>,
 ) {
     // Update display timer
     message_display.timer.tick(time.delta());
@@ -247,6 +255,10 @@ pub fn screenplay_system(
                     message_state.current_sequence = Some(sequence_id.to_string());
                     message_state.sequence_index = 0;
                     message_state.has_shown_current = false;
+
+                    if let Some((_, shot, anchor)) = SEQUENCE_SHOTS.iter().find(|(id, _, _)| *id == sequence_id) {
+                        camera_shots.send(PlayCameraShot { name: shot, anchor: *anchor });
+                    }
                 }
             }
 
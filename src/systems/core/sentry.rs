@@ -1,5 +1,5 @@
 use bevy::prelude::*;
-use crate::components::Sentry;
+use crate::components::{Sentry, Tank};
 use crate::components::Protagonist;
 use bevy::ecs::system::ParamSet;
 use rand;
@@ -10,8 +10,18 @@ use std::time::Duration;
 use crate::systems::core::minimap::{MinimapMarker, SentryMinimapMarker, MinimapResources, MINIMAP_MARKER_HEIGHT};
 use bevy::render::view::RenderLayers;
 use crate::systems::core::setup::ACQUIFIER_FLOOR_DEPTH;
+use crate::systems::core::screenplay::{MessageDisplay, display_message};
+use crate::systems::environments::time_of_day::TimeOfDay;
+use crate::systems::environments::weather::Weather;
+use crate::systems::environments::wind::WindField;
+use crate::systems::environments::tram::TramRider;
+use crate::systems::environments::terrain::TerrainHeightfield;
 
 const SENTRY_SPAWN_INTERVAL: f32 = 10.0; // Increased from 1.0 for less frequent spawns
+const SENTRY_SPAWN_COUNT: usize = 2;
+const SENTRY_SPAWN_CLEARANCE: f32 = 3.0;  // Above the terrain
+const SENTRY_SURGE_SPAWN_INTERVAL: f32 = 4.0;
+const SENTRY_SURGE_SPAWN_COUNT: usize = 4;
 
 // Sentry movement constants
 const SENTRY_VIEW_DISTANCE: f32 = 800.0;
@@ -23,6 +33,7 @@ const SENTRY_CLOSE_RANGE_THRESHOLD: f32 = 20.0;
 const SENTRY_MID_RANGE_THRESHOLD: f32 = 50.0;
 const SENTRY_VERTICAL_SPEED_MULTIPLIER: f32 = 0.7;
 const SENTRY_SCALE: f32 = 1.5;
+const SENTRY_INVESTIGATE_RADIUS: f32 = 10.0;  // Distance at which a sentry gives up on a heard noise
 
 // Explosion constants
 const EXPLOSION_MAX_ALLOWED: usize = 10;
@@ -36,6 +47,7 @@ const EXPLOSION_SMOKE_HEIGHT_MAX: f32 = 2000.0;
 const EXPLOSION_PARTICLE_LIFETIME: f32 = 160.0;
 const EXPLOSION_SMOKE_SPACING: f32 = 1.6;
 const EXPLOSION_INITIAL_PARTICLES: i32 = 10;
+const EXPLOSION_SMOKE_WIND_FACTOR: f32 = 0.5;  // How strongly rising smoke is blown sideways
 
 #[derive(Component)]
 pub struct ExplosionParticle {
@@ -64,6 +76,31 @@ pub struct SentryExplosion {
 #[derive(Component)]
 pub struct SentrySpawnTimer(Timer);
 
+// Loud sounds that sentries within `radius` will investigate
+#[derive(Event)]
+pub struct NoiseEvent {
+    pub position: Vec3,
+    pub radius: f32,
+}
+
+// A sentry knocked out by an EMP. It drops out of the air and wakes up when the timer runs out.
+#[derive(Component)]
+pub struct SentryStunned {
+    pub timer: Timer,
+}
+
+// Sent wherever a sentry blows up, so nearby vehicles can take damage
+#[derive(Event)]
+pub struct SentryExplosionEvent {
+    pub position: Vec3,
+}
+
+// While active, sentries spawn faster and in bigger groups (e.g. after the reactor is sabotaged)
+#[derive(Resource, Default)]
+pub struct SentrySurge {
+    pub active: bool,
+}
+
 // Add new resource to track active explosions
 #[derive(Resource)]
 pub struct ExplosionCounter {
@@ -89,6 +126,7 @@ pub struct ExplosionMaterials {
     glow_cone_mesh: Handle<Mesh>,
     glow_cone_red_material: Handle<StandardMaterial>,
     glow_cone_blue_material: Handle<StandardMaterial>,
+    glow_cone_dark_material: Handle<StandardMaterial>,
 }
 
 // Add new component for light cone animation
@@ -127,8 +165,26 @@ pub fn animate_light_cones(
     explosion_materials: Res<ExplosionMaterials>,
     mut query: Query<(&mut Transform, &mut LightConeAnimation, &mut Handle<StandardMaterial>, &Parent, &GlobalTransform)>,
     timing_query: Query<&SentryTiming>,
+    stunned_query: Query<(), With<SentryStunned>>,
 ) {
     for (mut transform, mut anim, mut material_handle, parent, _) in query.iter_mut() {
+        // Knocked-out sentries go dark until they recover
+        if stunned_query.contains(parent.get()) {
+            *material_handle = explosion_materials.glow_cone_dark_material.clone();
+            transform.scale = anim.base_scale;
+            continue;
+        }
+
+        // Back from being stunned: take the pulsing material again so the pulse below
+        // never writes into the shared dark one
+        if *material_handle == explosion_materials.glow_cone_dark_material {
+            *material_handle = if anim.is_red {
+                explosion_materials.glow_cone_red_material.clone()
+            } else {
+                explosion_materials.glow_cone_blue_material.clone()
+            };
+        }
+
         let time_offset = timing_query.get(parent.get()).map_or(0.0, |timing| timing.time_offset);
         let individual_time = time.elapsed_seconds() + time_offset;
         
@@ -189,6 +245,7 @@ fn spawn_sentry_at(
     materials: &ExplosionMaterials,
     minimap_resources: &Res<MinimapResources>,
     spatial_query: &SpatialQuery,
+    heightfield: &TerrainHeightfield,
     sentry_counter: &mut ResMut<SentryCounter>,
 ) -> bool {
     // Cast a ray down to find the ground position
@@ -206,9 +263,15 @@ fn spawn_sentry_at(
         filter.clone()
     ).first().copied();
 
+    // Terrain far from any body has no collider yet, so fall back to the heightfield.
     // If we don't find ground, don't spawn
+    let terrain_height = heightfield.height_at(position.x, position.z);
     let ground_position = if let Some(hit) = ground_hit {
         ray_start + Vec3::NEG_Y * hit.time_of_impact
+    } else if heightfield.surface_at(position.x, position.z).is_walkable()
+        && (0.0..max_distance).contains(&(ray_start.y - terrain_height))
+    {
+        Vec3::new(position.x, terrain_height, position.z)
     } else {
         return false;
     };
@@ -228,6 +291,8 @@ fn spawn_sentry_at(
             view_angle: SENTRY_VIEW_ANGLE,
             follow_speed: SENTRY_FOLLOW_SPEED,
             velocity: Vec3::ZERO,
+            last_heard: None,
+            tracking: None,
         },
         Name::new("Sentry"),
         SentryTiming {
@@ -281,21 +346,20 @@ pub fn spawn_sentry(
     explosion_materials: Res<ExplosionMaterials>,
     minimap_resources: Res<MinimapResources>,
     spatial_query: SpatialQuery,
+    heightfield: Res<TerrainHeightfield>,
     mut sentry_counter: ResMut<SentryCounter>,
 ) {
-    let sentry_position = Vec3::new(
-        PROTAGONIST_START.position.x + 300.0,
-        PROTAGONIST_START.position.y,
-        PROTAGONIST_START.position.z - 200.0
-    );
-    spawn_sentry_at(&mut commands, &asset_server, &mut meshes, sentry_position, &explosion_materials, &minimap_resources, &spatial_query, &mut sentry_counter);
+    let x = PROTAGONIST_START.position.x + 300.0;
+    let z = PROTAGONIST_START.position.z - 200.0;
+    let sentry_position = Vec3::new(x, heightfield.height_at(x, z) + SENTRY_SPAWN_CLEARANCE, z);
+    spawn_sentry_at(&mut commands, &asset_server, &mut meshes, sentry_position, &explosion_materials, &minimap_resources, &spatial_query, &heightfield, &mut sentry_counter);
 }
 
 pub fn sentry_follow_system(
     mut commands: Commands,
     mut query_set: ParamSet<(
-        Query<(&Transform, &Protagonist), With<Protagonist>>,
-        Query<(Entity, &mut Transform, &mut Sentry, &SentryTiming)>,
+        Query<(&Transform, &Protagonist, Option<&TramRider>), With<Protagonist>>,
+        Query<(Entity, &mut Transform, &mut Sentry, &SentryTiming), Without<SentryStunned>>,
     )>,
     time: Res<Time>,
     explosion_materials: Res<ExplosionMaterials>,
@@ -303,12 +367,15 @@ pub fn sentry_follow_system(
     mut materials: ResMut<Assets<StandardMaterial>>,
     asset_server: Res<AssetServer>,
     spatial_query: SpatialQuery,
+    mut explosion_events: EventWriter<SentryExplosionEvent>,
+    weather: Res<Weather>,
+    time_of_day: Res<TimeOfDay>,
 ) {
     // Get protagonist data first
-    let (protagonist_pos, is_driving) = {
+    let (protagonist_pos, is_driving, on_moving_tram) = {
         let protagonist_query = query_set.p0();
-        if let Ok((transform, protagonist)) = protagonist_query.get_single() {
-            (transform.translation, protagonist.is_driving)
+        if let Ok((transform, protagonist, tram_rider)) = protagonist_query.get_single() {
+            (transform.translation, protagonist.is_driving, tram_rider.map_or(false, |rider| rider.moving))
         } else {
             return;
         }
@@ -358,14 +425,15 @@ pub fn sentry_follow_system(
                             &time,
                         );
                     }
+                    // One blast per collision, so nearby tanks aren't damaged twice
+                    explosion_events.send(SentryExplosionEvent { position: transform.translation });
                     return;
                 }
             }
         }
 
         let individual_time = time.elapsed_seconds() + timing.time_offset;
-        let direction = protagonist_pos - transform.translation;
-        let distance = direction.length();
+        let distance = (protagonist_pos - transform.translation).length();
 
         // Update speed multiplier based on driving state
         let driving_multiplier = if is_driving { 5.0 } else { 1.0 };
@@ -379,9 +447,27 @@ pub fn sentry_follow_system(
             1.0
         }) * driving_multiplier;  // Apply driving multiplier here
 
-        if distance < sentry.view_distance {
+        // Chase the protagonist when in view, otherwise investigate the last noise heard.
+        // Sentries can't keep up with a moving tram, so they lose track of its riders,
+        // and bad weather and darkness cut how far they can see.
+        let vision = weather.sentry_vision() * time_of_day.sentry_vision();
+        let target = if distance < sentry.view_distance * vision && !on_moving_tram {
+            sentry.last_heard = None;
+            Some(protagonist_pos)
+        } else if let Some(heard) = sentry.last_heard {
+            if (heard - transform.translation).length() < SENTRY_INVESTIGATE_RADIUS {
+                sentry.last_heard = None;
+            }
+            Some(heard)
+        } else {
+            None
+        };
+
+        if let Some(target) = target {
+            let direction = target - transform.translation;
+
             // Split movement into horizontal and vertical components
-            let horizontal_direction = Vec3::new(direction.x, 0.0, direction.z).normalize();
+            let horizontal_direction = Vec3::new(direction.x, 0.0, direction.z).normalize_or_zero();
             let vertical_movement = direction.y;
 
             // Check for vertical surface contact using raycasts
@@ -439,7 +525,7 @@ pub fn sentry_follow_system(
             // Update rotation with wobble effect
             if direction.length_squared() > 0.001 {
                 let wobble = Quat::from_rotation_z((individual_time * 12.0).sin() * 0.25); // Faster, more pronounced wobble
-                transform.look_at(protagonist_pos, Vec3::Y);
+                transform.look_at(target, Vec3::Y);
                 transform.rotation *= wobble;
             }
             
@@ -475,6 +561,7 @@ pub fn sentry_follow_system(
                     &mut materials,
                     &time,
                 );
+                explosion_events.send(SentryExplosionEvent { position: transform.translation });
             }
         }
     }
@@ -516,6 +603,12 @@ pub fn setup_explosion_materials(
             double_sided: true, // Add this for sphere visibility
             ..default()
         }),
+        glow_cone_dark_material: materials.add(StandardMaterial {
+            base_color: Color::srgba(0.05, 0.05, 0.05, 0.1),  // Knocked out, no glow
+            alpha_mode: AlphaMode::Blend,
+            double_sided: true,
+            ..default()
+        }),
     };
     commands.insert_resource(materials);
     commands.insert_resource(ExplosionCounter::default());
@@ -523,7 +616,7 @@ pub fn setup_explosion_materials(
 }
 
 // Update spawn_explosion_effects to be more efficient
-fn spawn_explosion_effects(
+pub fn spawn_explosion_effects(
     commands: &mut Commands,
     explosion_materials: &ExplosionMaterials,
     position: Vec3,
@@ -619,7 +712,9 @@ pub fn periodic_sentry_spawn(
     explosion_materials: Res<ExplosionMaterials>,
     minimap_resources: Res<MinimapResources>,
     spatial_query: SpatialQuery,
+    heightfield: Res<TerrainHeightfield>,
     mut sentry_counter: ResMut<SentryCounter>,
+    surge: Res<SentrySurge>,
 ) {
     // Initialize timer if it doesn't exist
     if timer_query.is_empty() {
@@ -627,13 +722,19 @@ pub fn periodic_sentry_spawn(
         return;
     }
 
+    let (spawn_interval, spawn_count) = if surge.active {
+        (SENTRY_SURGE_SPAWN_INTERVAL, SENTRY_SURGE_SPAWN_COUNT)
+    } else {
+        (SENTRY_SPAWN_INTERVAL, SENTRY_SPAWN_COUNT)
+    };
+
     let mut timer = timer_query.single_mut();
+    timer.0.set_duration(Duration::from_secs_f32(spawn_interval));
     timer.0.tick(time.delta());
 
     if timer.0.just_finished() {
         if let Ok((protagonist_transform, protagonist)) = protagonist_query.get_single() {
-            // Try to spawn 2 sentries instead of 3
-            for _ in 0..2 {
+            for _ in 0..spawn_count {
                 for _ in 0..5 {
                     let angle = rand::random::<f32>() * PI / 1.5 - PI / 3.0;
                     let distance = 60.0 + rand::random::<f32>() * 100.0;
@@ -646,7 +747,10 @@ pub fn periodic_sentry_spawn(
                         // Spawn near the acquifier floor
                         ACQUIFIER_FLOOR_DEPTH + 10.0 + rand::random::<f32>() * 20.0
                     } else {
-                        3.0 // Default spawn height
+                        heightfield.height_at(
+                            protagonist_transform.translation.x + spawn_direction.x * distance,
+                            protagonist_transform.translation.z + spawn_direction.z * distance,
+                        ) + SENTRY_SPAWN_CLEARANCE
                     };
                     
                     let spawn_pos = Vec3::new(
@@ -655,7 +759,7 @@ pub fn periodic_sentry_spawn(
                         protagonist_transform.translation.z + spawn_direction.z * distance,
                     );
                     
-                    if spawn_sentry_at(&mut commands, &asset_server, &mut meshes, spawn_pos, &explosion_materials, &minimap_resources, &spatial_query, &mut sentry_counter) {
+                    if spawn_sentry_at(&mut commands, &asset_server, &mut meshes, spawn_pos, &explosion_materials, &minimap_resources, &spatial_query, &heightfield, &mut sentry_counter) {
                         break;
                     }
                 }
@@ -667,6 +771,7 @@ pub fn periodic_sentry_spawn(
 pub fn update_explosion_particles(
     mut commands: Commands,
     time: Res<Time>,
+    wind: Res<WindField>,
     mut query: Query<(Entity, &mut Transform, &mut ExplosionParticle, &Handle<StandardMaterial>)>,
     mut materials: ResMut<Assets<StandardMaterial>>,
     mut explosion_counter: ResMut<ExplosionCounter>,
@@ -740,7 +845,10 @@ pub fn update_explosion_particles(
                 ((time.elapsed_seconds() + particle.start_time) * 0.8).cos() * (0.1 + chaos_factor * 2.0)
             );
             
-            transform.translation += (particle.velocity * fade_factor + wobble) * time.delta_seconds();
+            // Higher smoke is out of the shelter of the ground and drifts further downwind
+            let drift = wind.sample(transform.translation) * EXPLOSION_SMOKE_WIND_FACTOR * (0.2 + height_fraction);
+
+            transform.translation += (particle.velocity * fade_factor + wobble + drift) * time.delta_seconds();
             // Particles grow larger as they rise
             transform.scale = Vec3::splat(particle.initial_scale * (1.0 + height_fraction * 2.0) * fade_factor);
             
@@ -783,6 +891,89 @@ pub fn update_explosion_light(
     }
 }
 
+// Point sentries within earshot at the source of a noise
+pub fn sentry_hear_noise(
+    mut noise_events: EventReader<NoiseEvent>,
+    mut sentry_query: Query<(&Transform, &mut Sentry)>,
+) {
+    for noise in noise_events.read() {
+        for (transform, mut sentry) in sentry_query.iter_mut() {
+            if transform.translation.distance(noise.position) < noise.radius {
+                sentry.last_heard = Some(noise.position);
+            }
+        }
+    }
+}
+
+// Sentries that can see a parked, empty tank go and check it out
+pub fn sentry_notice_empty_tanks(
+    mut sentry_query: Query<(&Transform, &mut Sentry)>,
+    mut tank_query: Query<(&Transform, &mut Tank)>,
+    mut message_display: ResMut<MessageDisplay>,
+    weather: Res<Weather>,
+    time_of_day: Res<TimeOfDay>,
+) {
+    let vision = weather.sentry_vision() * time_of_day.sentry_vision();
+
+    for (tank_transform, mut tank) in tank_query.iter_mut() {
+        if !tank.unattended {
+            continue;
+        }
+
+        for (transform, mut sentry) in sentry_query.iter_mut() {
+            if sentry.last_heard.is_none()
+                && transform.translation.distance(tank_transform.translation) < sentry.view_distance * vision
+            {
+                sentry.last_heard = Some(tank_transform.translation);
+                tank.unattended = false;
+            }
+        }
+
+        if !tank.unattended {
+            display_message("A SENTRY SPOTTED THE TANK", Color::srgb(0.99, 0.2, 0.2), &mut message_display);
+        }
+    }
+}
+
+// Knock out sentries within `radius`, letting them fall with physics
+pub fn stun_sentries(
+    commands: &mut Commands,
+    sentry_query: &Query<(Entity, &Transform), (With<Sentry>, Without<SentryStunned>)>,
+    position: Vec3,
+    radius: f32,
+    duration: f32,
+) -> usize {
+    let mut stunned = 0;
+    for (entity, transform) in sentry_query.iter() {
+        if transform.translation.distance(position) < radius {
+            commands.entity(entity).insert((
+                SentryStunned {
+                    timer: Timer::from_seconds(duration, TimerMode::Once),
+                },
+                RigidBody::Dynamic,
+                Collider::sphere(SENTRY_SCALE),
+            ));
+            stunned += 1;
+        }
+    }
+    stunned
+}
+
+pub fn recover_stunned_sentries(
+    mut commands: Commands,
+    mut stunned_query: Query<(Entity, &mut SentryStunned, &mut Sentry)>,
+    time: Res<Time>,
+) {
+    for (entity, mut stunned, mut sentry) in stunned_query.iter_mut() {
+        if stunned.timer.tick(time.delta()).finished() {
+            sentry.velocity = Vec3::ZERO;
+            commands.entity(entity)
+                .remove::<SentryStunned>()
+                .remove::<(RigidBody, Collider)>();
+        }
+    }
+}
+
 // Add system to update counter when sentries are destroyed
 pub fn update_sentry_counter(
     mut sentry_counter: ResMut<SentryCounter>,
@@ -1,4 +1,7 @@
-use crate::components::Protagonist;
+use crate::components::{Protagonist, Health, Inventory};
+use crate::systems::player::falling::FallTracker;
+use crate::systems::core::camera::PROTAGONIST_RENDER_LAYER;
+use crate::systems::environments::time_of_day::{Sun, SkyDome};
 use crate::resources::ProtagonistAnimations;
 use crate::systems::environments::ice_cave::spawn_ice_cave;
 use crate::systems::environments::launch_silo::spawn_launch_silo;
@@ -18,6 +21,7 @@ use bevy::{
 use bevy::math::Vec3;
 use bevy::render::texture::{ImageSampler, ImageAddressMode, ImageSamplerDescriptor};
 use bevy::render::view::RenderLayers;
+use crate::systems::environments::surface::GroundSurface;
 
 // Constants for structure dimensions
 
@@ -40,7 +44,6 @@ pub fn setup(
     mut materials: ResMut<Assets<StandardMaterial>>,
     mut graphs: ResMut<Assets<AnimationGraph>>,
     mut images: ResMut<Assets<Image>>,
-    time: Res<Time>,
 ) {
 
     // Protagonist animations
@@ -62,7 +65,7 @@ pub fn setup(
         graph: protagonist_graph.clone(),
     });
 
-    // Add Camera and Ambient Lighting
+    // Add Camera
 
     commands.spawn((
         Camera3dBundle {
@@ -79,16 +82,10 @@ pub fn setup(
             diffuse_map: asset_server.load("environment_maps/pisa_diffuse_rgb9e5_zstd.ktx2"),
             specular_map: asset_server.load("environment_maps/pisa_specular_rgb9e5_zstd.ktx2"),
             intensity: 5.0,
-        }
-
+        },
+        RenderLayers::from_layers(&[0, PROTAGONIST_RENDER_LAYER]),
     ));
 
-    // Add Ambient Light
-    commands.insert_resource(AmbientLight {
-        color: Color::srgb(0.1, 0.1, 0.3),
-        brightness: 5.0,
-    });
-
     // Add Directional Lighting
 
     commands.spawn(DirectionalLightBundle {
@@ -109,7 +106,9 @@ pub fn setup(
         }
         .into(),
         ..default()
-    });
+    })
+    // Light the protagonist's own render layer too so it still casts shadows
+    .insert((RenderLayers::from_layers(&[0, PROTAGONIST_RENDER_LAYER]), Sun));
 
     // Static "floor"
 
@@ -191,6 +190,7 @@ pub fn setup(
         ExternalImpulse::default(),
         LockedAxes::new().lock_rotation_x().lock_rotation_z(),
         Friction::new(0.5),
+        GroundSurface::default(),
         GravityScale(3.0),
         Protagonist { 
             is_climbing: false,
@@ -202,9 +202,14 @@ pub fn setup(
             is_dirigible: false,
             is_outside: false,
             is_birds_eye: false,
+            is_first_person: false,
             last_climb_toggle: 0.0,
             is_jumping: false,
+            is_crawling: false,
         },
+        Health::new(100.0),
+        FallTracker::default(),
+        Inventory::default(),
         SceneBundle {       
             scene: asset_server
                 .load(GltfAssetLabel::Scene(0)
@@ -216,7 +221,7 @@ pub fn setup(
         Name::new("Protagonist"),
     )).with_children(|parent| {
         // Add spotlight as child of protagonist
-        parent.spawn(SpotLightBundle {
+        parent.spawn((SpotLightBundle {
             transform: Transform::from_xyz(0.0, 15.0, 2.0) // Higher and slightly behind
                 .looking_at(Vec3::new(0.0, 0.0, -10.0), Vec3::Y), // Point forward and down
             spot_light: SpotLight {
@@ -229,7 +234,7 @@ pub fn setup(
                 ..default()
             },
             ..default()
-        });
+        }, RenderLayers::from_layers(&[0, PROTAGONIST_RENDER_LAYER])));
     });
 
     // Load the stars texture
@@ -254,6 +259,7 @@ pub fn setup(
             ..default()
         },
         Name::new("SkyDome"),
+        SkyDome,
     ));
 
     // Large white cylinder tundra
@@ -343,7 +349,7 @@ pub fn setup(
     )).insert(Transform::from_xyz(0.0, -5.2, 0.0));  // 5 units below SubFloor
 
 
-    spawn_ice_cave(&mut commands, &mut meshes, &mut materials, &asset_server, &time);
+    spawn_ice_cave(&mut commands, &mut meshes, &mut materials, &asset_server);
 
     spawn_reactor(&mut commands, &mut meshes, &mut materials, &asset_server);
 
@@ -368,6 +374,8 @@ pub fn setup(
             view_angle: std::f32::consts::PI / 2.0,
             follow_speed: 10.0,
             velocity: Vec3::ZERO,
+            last_heard: None,
+            tracking: None,
         },
         Name::new("InitialSentry"),
         SentryTiming {
@@ -2,15 +2,16 @@ use bevy::prelude::*;
 use avian3d::prelude::*;
 
 use crate::systems::core::setup::{WORLD_RADIUS, PERIMETER_WALL_HEIGHT, ACQUIFIER_FLOOR_DEPTH};
-use crate::systems::player::dirigible::DirigibleBalloon;
+use crate::systems::player::dirigible::attach_balloon;
 use crate::systems::core::screenplay::{MessageDisplay, display_message};
 use crate::components::Protagonist;
+use crate::systems::core::cinematics::PlayCameraShot;
+use crate::systems::core::interaction::{Interactable, InteractEvent};
 
 // Add new component
 #[derive(Component)]
 pub struct AcquifierDirigibleTrigger {
     pub position: Vec3,
-    pub radius: f32,
     pub entity: Entity,
 }
 
@@ -88,15 +89,16 @@ pub fn spawn_acquifier(
         },
         AcquifierDirigibleTrigger {
             position: sphere_pos,
-            radius: sphere_radius,
             entity: sphere_entity,
-        }
+        },
+        Interactable::new("board the dirigible", sphere_radius * 2.5),
     ));
 }
 
 // Add new system to check for trigger and handle transition
 pub fn check_acquifier_dirigible_trigger(
-    trigger_query: Query<(Entity, &AcquifierDirigibleTrigger)>,
+    mut interact_events: EventReader<InteractEvent>,
+    trigger_query: Query<&AcquifierDirigibleTrigger>,
     mut player_query: Query<(Entity, &Transform, &mut Protagonist)>,
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
@@ -104,65 +106,56 @@ pub fn check_acquifier_dirigible_trigger(
     asset_server: Res<AssetServer>,
     mut message_display: ResMut<MessageDisplay>,
     time: Res<Time>,
+    mut camera_shots: EventWriter<PlayCameraShot>,
 ) {
-    if let (Ok((trigger_entity, trigger)), Ok((player_entity, player_transform, mut protagonist))) = (
-        trigger_query.get_single(),
-        player_query.get_single_mut()
-    ) {
-        let player_pos = player_transform.translation;
-        let horizontal_dist = Vec2::new(
-            player_pos.x - trigger.position.x,
-            player_pos.z - trigger.position.z
-        ).length();
+    for event in interact_events.read() {
+        let (Ok(trigger), Ok((player_entity, player_transform, mut protagonist))) = (
+            trigger_query.get(event.target),
+            player_query.get_single_mut()
+        ) else {
+            continue;
+        };
 
-        if horizontal_dist < trigger.radius * 1.5 && player_pos.y < trigger.position.y {
-            if !protagonist.is_dirigible {
-                // Display message
-                display_message(
-                    "DIRIGIBLE MODE ACTIVATED - SPACE TO FLOAT, SHIFT TO DESCEND",
-                    Color::WHITE,
-                    &mut message_display
-                );
+        if protagonist.is_dirigible {
+            continue;
+        }
 
-                protagonist.is_dirigible = true;
-                protagonist.is_swimming = false;
-                protagonist.is_falling = false;
-                protagonist.is_climbing = false;
+        // Display message
+        display_message(
+            "DIRIGIBLE MODE ACTIVATED - SHIFT TO DROP BALLAST, SPACE TO VENT GAS",
+            Color::WHITE,
+            &mut message_display
+        );
 
-                // Spawn the dirigible balloon (10x larger)
-                commands.entity(player_entity).with_children(|parent| {
-                    parent.spawn((
-                        PbrBundle {
-                            mesh: meshes.add(Mesh::from(Sphere::new(200.0))), // 10x larger radius
-                            material: materials.add(StandardMaterial {
-                                base_color: Color::srgb(1.0, 1.0, 1.0),
-                                base_color_texture: Some(asset_server.load("textures/american-flag-background.png")),
-                                metallic: 0.8,
-                                perceptual_roughness: 0.1,
-                                reflectance: 0.7,
-                                ..default()
-                            }),
-                            transform: Transform::from_xyz(0.0, 300.0, 0.0), // 10x higher position
-                            ..default()
-                        },
-                        DirigibleBalloon,
-                    ));
-                });
+        // Spawn the dirigible balloon (10x larger)
+        attach_balloon(
+            &mut commands,
+            &mut meshes,
+            &mut materials,
+            &asset_server,
+            player_entity,
+            &mut protagonist,
+            200.0,
+            300.0,  // 10x higher position
+        );
 
-                // Add smooth animation component and disable collisions
-                commands.entity(player_entity)
-                    .insert(DirigibleTransition {
-                        start_pos: player_transform.translation,
-                        target_pos: player_transform.translation + Vec3::new(0.0, 4000.0, 0.0), // 10x higher animation
-                        start_time: time.elapsed_seconds(),
-                        duration: 5.0, // 5 second animation
-                    })
-                    .remove::<Collider>(); // Remove collider during transition
+        // Add smooth animation component and disable collisions
+        commands.entity(player_entity)
+            .insert(DirigibleTransition {
+                start_pos: player_transform.translation,
+                target_pos: player_transform.translation + Vec3::new(0.0, 4000.0, 0.0), // 10x higher animation
+                start_time: time.elapsed_seconds(),
+                duration: 5.0, // 5 second animation
+            })
+            .remove::<Collider>(); // Remove collider during transition
 
-                // Despawn trigger sphere
-                commands.entity(trigger_entity).despawn_recursive();
-            }
-        }
+        camera_shots.send(PlayCameraShot {
+            name: "dirigible_liftoff",
+            anchor: trigger.position,
+        });
+
+        // Despawn trigger sphere
+        commands.entity(event.target).despawn_recursive();
     }
 }
 
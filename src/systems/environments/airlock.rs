@@ -1,13 +1,16 @@
 use bevy::prelude::*;
-use bevy::math::primitives::{Cylinder, Sphere};
+use bevy::math::primitives::{Annulus, Sphere};
 use avian3d::prelude::*;
-use crate::components::Protagonist;
-use crate::systems::core::screenplay::{MessageDisplay, display_message};
+use crate::systems::environments::doors::{spawn_door, DoorConfig, Door};
+use crate::systems::environments::surface::SurfaceMaterial;
 
 pub const AIRLOCK_RADIUS: f32 = 16.0;
 pub const AIRLOCK_LENGTH: f32 = 800.0;
 pub const AIRLOCK_POSITION: Vec3 = Vec3::new(865.24176, 2.6249726, -424.97363);
-pub const AIRLOCK_TELEPORT_OFFSET: f32 = 20.0;
+const AIRLOCK_WALL_THICKNESS: f32 = 2.0;
+const AIRLOCK_FLOOR_TOP: f32 = 0.5;  // Just above the leveled ground the tube is bedded in
+const AIRLOCK_CYCLE_TIME: f32 = 4.0;  // Pressurization before the door opens
+const AIRLOCK_KEYCARD: Option<&str> = Some("keycard_airlock");  // Only needed on the way in
 
 #[derive(Component)]
 pub struct AirlockLight {
@@ -15,10 +18,7 @@ pub struct AirlockLight {
 }
 
 #[derive(Component)]
-pub struct AirlockTrigger {
-    pub is_entry: bool,
-    pub cooldown: Timer,
-}
+pub struct AirlockDoor;
 
 pub fn spawn_airlock(
     mut commands: Commands,
@@ -26,28 +26,55 @@ pub fn spawn_airlock(
     mut materials: ResMut<Assets<StandardMaterial>>,
     asset_server: Res<AssetServer>,
 ) {
-    // Main tunnel cylinder
+    // Main tunnel, hollow so it can be walked through from door to door
     commands.spawn((
         RigidBody::Static,
-        Collider::cylinder(AIRLOCK_RADIUS, AIRLOCK_LENGTH),
+        ColliderConstructor::TrimeshFromMesh,
+        SurfaceMaterial::Metal,
         PbrBundle {
-            mesh: meshes.add(Cylinder {
-                radius: AIRLOCK_RADIUS,
-                half_height: AIRLOCK_LENGTH/2.0,
-            }),
+            mesh: meshes.add(Extrusion::new(
+                Annulus::new(AIRLOCK_RADIUS, AIRLOCK_RADIUS + AIRLOCK_WALL_THICKNESS),
+                AIRLOCK_LENGTH,
+            )),
             material: materials.add(StandardMaterial {
                 base_color_texture: Some(asset_server.load("textures/rusty_metal_grid_diff_4k.png")),
                 perceptual_roughness: 0.8,
                 metallic: 0.7,
+                double_sided: true,
+                cull_mode: None,
                 ..default()
             }),
             transform: Transform::from_translation(AIRLOCK_POSITION)
-                .with_rotation(Quat::from_rotation_z(std::f32::consts::FRAC_PI_2)),
+                .with_rotation(Quat::from_rotation_y(std::f32::consts::FRAC_PI_2)),  // Extruded along X
             ..default()
         },
         Name::new("AirlockTunnel"),
     ));
 
+    // Grated floor along the bottom of the tunnel
+    let floor_size = Vec3::new(AIRLOCK_LENGTH, 1.0, AIRLOCK_RADIUS * 1.8);
+    commands.spawn((
+        RigidBody::Static,
+        Collider::cuboid(floor_size.x, floor_size.y, floor_size.z),
+        SurfaceMaterial::Metal,
+        PbrBundle {
+            mesh: meshes.add(Cuboid::new(floor_size.x, floor_size.y, floor_size.z)),
+            material: materials.add(StandardMaterial {
+                base_color_texture: Some(asset_server.load("textures/rusty_metal_grid_diff_4k.png")),
+                perceptual_roughness: 0.8,
+                metallic: 0.7,
+                ..default()
+            }),
+            transform: Transform::from_xyz(
+                AIRLOCK_POSITION.x,
+                AIRLOCK_FLOOR_TOP - floor_size.y / 2.0,
+                AIRLOCK_POSITION.z,
+            ),
+            ..default()
+        },
+        Name::new("AirlockFloor"),
+    ));
+
     // Add blinking lights at both ends with intense red
     for offset in [-AIRLOCK_LENGTH/2.0, AIRLOCK_LENGTH/2.0] {
         commands.spawn((
@@ -75,58 +102,26 @@ pub fn spawn_airlock(
         ));
     }
 
-    // Add metallic doors at both ends
+    // Add pressurized doors sealing both ends of the tunnel
     for offset in [-AIRLOCK_LENGTH/2.0, AIRLOCK_LENGTH/2.0] {
-        let door_height = AIRLOCK_RADIUS * 0.6;  // half-height of the door
-        commands.spawn((
-            PbrBundle {
-                mesh: meshes.add(Cuboid::new(
-                    AIRLOCK_RADIUS * 0.05,     // thickness (halved)
-                    door_height,               // height (halved)
-                    AIRLOCK_RADIUS * 0.6,      // width (halved)
-                )),
-                material: materials.add(StandardMaterial {
-                    base_color_texture: Some(asset_server.load("textures/airlock_door.png")),
-                    metallic: 1.0,
-                    perceptual_roughness: 0.2,
-                    emissive: Color::srgb(0.1, 0.0, 0.0).into(), 
-                    ..default()
-                }),
-                transform: Transform::from_translation(AIRLOCK_POSITION + Vec3::new(offset, door_height/2.0, 0.0))  // Changed to door_height/2.0
-                    .with_rotation(Quat::from_rotation_x(std::f32::consts::FRAC_PI_2)),
-                ..default()
-            },
-            Name::new("AirlockDoor"),
-        ));
-    }
-
-    // Add trigger volumes at both ends - now using disc-shaped sensors
-    for (offset, is_entry) in [(-AIRLOCK_LENGTH/2.0, true), (AIRLOCK_LENGTH/2.0, false)] {
-        let transform = Transform::from_translation(AIRLOCK_POSITION + Vec3::new(offset, 0.0, 0.0))
-            .with_rotation(Quat::from_rotation_z(std::f32::consts::FRAC_PI_2));
-
-        commands.spawn((
-            Sensor,
-            Collider::cylinder(1.2, AIRLOCK_RADIUS),
-            PbrBundle {
-                mesh: meshes.add(Cylinder {
-                    radius: AIRLOCK_RADIUS,
-                    half_height: 1.1,
-                }),
-                material: materials.add(StandardMaterial {
-                    base_color: Color::srgba(1.0, 0.0, 0.0, 0.01),
-                    alpha_mode: AlphaMode::Blend,
-                    ..default()
-                }),
-                transform,
-                ..default()
+        let is_entry = offset < 0.0;
+        let side = offset.signum();
+        let door = spawn_door(
+            &mut commands,
+            &mut meshes,
+            &mut materials,
+            &asset_server,
+            DoorConfig {
+                position: Vec3::new(AIRLOCK_POSITION.x + offset + side, 0.0, AIRLOCK_POSITION.z),
+                rotation: Quat::from_rotation_y(-side * std::f32::consts::FRAC_PI_2),  // Face out of the tube
+                size: Vec3::new(AIRLOCK_RADIUS * 2.0, AIRLOCK_POSITION.y + AIRLOCK_RADIUS, 1.0),  // Covers the opening above the floor
+                texture: "textures/airlock_door.png",
+                keycard: if is_entry { AIRLOCK_KEYCARD } else { None },
+                cycle_time: Some(AIRLOCK_CYCLE_TIME),
+                passage: None,
             },
-            AirlockTrigger { 
-                is_entry,
-                cooldown: Timer::from_seconds(1.0, TimerMode::Once),
-            },
-            Name::new("AirlockTrigger"),
-        ));
+        );
+        commands.entity(door).insert((AirlockDoor, Name::new("AirlockDoor")));
     }
 
     // Add spotlights at both ends - Adjusting position and intensity
@@ -152,87 +147,36 @@ pub fn spawn_airlock(
     });
 }
 
+// The airlock lights only blink while a door is pressurizing
 pub fn blink_airlock_light(
     time: Res<Time>,
+    door_query: Query<&Door, With<AirlockDoor>>,
     mut lights: Query<(&mut AirlockLight, Option<&mut Handle<StandardMaterial>>, Option<&mut PointLight>)>,
     mut materials: ResMut<Assets<StandardMaterial>>,
 ) {
+    let is_cycling = door_query.iter().any(|door| door.is_cycling());
+
     for (mut light, material_handle, mut point_light) in &mut lights {
         light.timer.tick(time.delta());
-        if light.timer.just_finished() {
-            if let Some(material_handle) = material_handle {
-                if let Some(material) = materials.get_mut(&*material_handle) {
-                    let is_on = material.emissive.red > 0.0;
-                    material.emissive = if is_on {
-                        LinearRgba::new(0.0, 0.0, 0.0, 1.0)
-                    } else {
-                        LinearRgba::new(5.0, 0.0, 0.0, 1.0)
-                    };
-                    
-                    if let Some(point_light) = &mut point_light {
-                        point_light.intensity = if is_on { 0.0 } else { 1000.0 };
-                    }
-                }
+        if let Some(material_handle) = material_handle {
+            let is_on = materials.get(&*material_handle).map_or(false, |material| material.emissive.red > 0.0);
+            // Blink on the timer while cycling, otherwise switch off once
+            let should_toggle = if is_cycling { light.timer.just_finished() } else { is_on };
+            if !should_toggle {
+                continue;
             }
-        }
-    }
-}
 
-pub fn handle_airlock_teleport(
-    time: Res<Time>,
-    collisions: Res<Collisions>,
-    mut triggers: Query<(Entity, &mut AirlockTrigger)>,
-    mut player: Query<(Entity, &mut Transform), With<Protagonist>>,
-    mut velocity_query: Query<&mut LinearVelocity, With<Protagonist>>,
-    mut message_display: ResMut<MessageDisplay>,
-) {
-    if let Ok((player_entity, mut player_transform)) = player.get_single_mut() {
-        for (trigger_entity, mut trigger) in &mut triggers {
-            if collisions.contains(player_entity, trigger_entity) {
-                // If the cooldown is finished, teleport immediately
-                if trigger.cooldown.finished() {
-                    // Display appropriate message based on entry/exit
-                    if trigger.is_entry {
-                        display_message("Shelter in the vehicle", Color::WHITE, &mut message_display);
-                    } else {
-                        display_message("Shelter in the vehicle", Color::WHITE, &mut message_display);
-                    }
-                    
-                    // Small delay to allow message to be visible
-                    std::thread::sleep(std::time::Duration::from_millis(100));
-                    
-                    println!("Teleporting player! is_entry: {}", trigger.is_entry);
-                    teleport_player(&mut player_transform, &mut velocity_query, trigger.is_entry);
-                    
-                    trigger.cooldown.reset();
+            if let Some(material) = materials.get_mut(&*material_handle) {
+                material.emissive = if is_on {
+                    LinearRgba::new(0.0, 0.0, 0.0, 1.0)
+                } else {
+                    LinearRgba::new(5.0, 0.0, 0.0, 1.0)
+                };
+                
+                if let Some(point_light) = &mut point_light {
+                    point_light.intensity = if is_on { 0.0 } else { 1000.0 };
                 }
-                // Tick the timer while in contact
-                trigger.cooldown.tick(time.delta());
             }
         }
     }
 }
-
-fn teleport_player(
-    player_transform: &mut Transform, 
-    velocity_query: &mut Query<&mut LinearVelocity, With<Protagonist>>, 
-    is_entry: bool
-) {
-    // Calculate the target x position relative to the airlock
-    let target_x = if is_entry {
-        AIRLOCK_POSITION.x + AIRLOCK_LENGTH/2.0 + AIRLOCK_TELEPORT_OFFSET
-    } else {
-        AIRLOCK_POSITION.x - AIRLOCK_LENGTH/2.0 - AIRLOCK_TELEPORT_OFFSET
-    };
-
-    // Keep current z, update x and add vertical offset
-    player_transform.translation.x = target_x;
-    player_transform.translation.y += 5.0;  // Add 5 units of vertical offset
-
-    // Preserve vertical and lateral movement, only zero out forward velocity
-    if let Ok(mut linear_velocity) = velocity_query.get_single_mut() {
-        linear_velocity.0.x = 0.0;  // Zero out only the x component
-        linear_velocity.0.y *= 0.25;
-        linear_velocity.0.z *= 0.25;
-    }
-}
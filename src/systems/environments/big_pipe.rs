@@ -4,6 +4,8 @@ use crate::systems::core::setup::ACQUIFIER_FLOOR_DEPTH;
 use rand::Rng;
 use crate::components::Protagonist;
 use crate::systems::core::screenplay::{MessageDisplay, display_message};
+use crate::systems::core::interaction::{Interactable, InteractEvent};
+use crate::systems::environments::surface::SurfaceMaterial;
 
 // Constants for the pipe
 const PIPE_INNER_RADIUS: f32 = 100.0;
@@ -12,12 +14,11 @@ const PIPE_RADIUS: f32 = PIPE_INNER_RADIUS + PIPE_THICKNESS;
 const PIPE_LENGTH: f32 = 2200.0;
 const PIPE_BOTTOM_GAP: f32 = 200.0;
 // Moved another 500 units closer to origin on both X and Z
+const PIPE_LIFT_INTERACT_RANGE: f32 = 250.0;
 pub const PIPE_POSITION: Vec3 = Vec3::new(-7447.827, ACQUIFIER_FLOOR_DEPTH + PIPE_BOTTOM_GAP + PIPE_LENGTH/2.0, 9928.124);
 
 #[derive(Component)]
-pub struct PipeLiftTrigger {
-    pub cooldown: Timer,
-}
+pub struct PipeLiftTrigger;
 
 pub fn spawn_big_pipe(
     mut commands: Commands,
@@ -55,6 +56,7 @@ pub fn spawn_big_pipe(
         },
         RigidBody::Static,
         ColliderConstructor::TrimeshFromMesh,
+        SurfaceMaterial::Metal,
     ))
     .with_children(|parent| {
         // Add trigger cylinder as child of pipe
@@ -77,9 +79,8 @@ pub fn spawn_big_pipe(
                     .with_rotation(Quat::from_rotation_x(std::f32::consts::FRAC_PI_2)),
                 ..default()
             },
-            PipeLiftTrigger {
-                cooldown: Timer::from_seconds(1.0, TimerMode::Once),
-            },
+            PipeLiftTrigger,
+            Interactable::new("ride the lift", PIPE_LIFT_INTERACT_RANGE),
             Name::new("PipeLiftTrigger"),
         ));
     });
@@ -180,28 +181,25 @@ pub fn spawn_big_pipe(
 
 // Add this system to main.rs
 pub fn handle_pipe_lift(
-    time: Res<Time>,
-    collisions: Res<Collisions>,
-    mut triggers: Query<(Entity, &mut PipeLiftTrigger)>,
-    mut player: Query<(Entity, &mut Transform, &mut LinearVelocity), With<Protagonist>>,
+    mut interact_events: EventReader<InteractEvent>,
+    triggers: Query<(), With<PipeLiftTrigger>>,
+    mut player: Query<(&mut Transform, &mut LinearVelocity), With<Protagonist>>,
     mut message_display: ResMut<MessageDisplay>,
 ) {
-    if let Ok((player_entity, mut player_transform, mut velocity)) = player.get_single_mut() {
-        for (trigger_entity, mut trigger) in &mut triggers {
-            if collisions.contains(player_entity, trigger_entity) {
-                if trigger.cooldown.finished() {
-                    // Zero out vertical velocity
-                    velocity.0.y = 0.0;
-                    
-                    // Translate player
-                    player_transform.translation.y += 750.0;
-                    player_transform.translation.x += 500.0;
-                    
-                    display_message("Shelter in the vehicle...", Color::rgb(0.0, 0.8, 1.0), &mut message_display);
-                    trigger.cooldown.reset();
-                }
-                trigger.cooldown.tick(time.delta());
-            }
+    for event in interact_events.read() {
+        if triggers.get(event.target).is_err() {
+            continue;
+        }
+
+        if let Ok((mut player_transform, mut velocity)) = player.get_single_mut() {
+            // Zero out vertical velocity
+            velocity.0.y = 0.0;
+            
+            // Translate player
+            player_transform.translation.y += 750.0;
+            player_transform.translation.x += 500.0;
+            
+            display_message("Shelter in the vehicle...", Color::rgb(0.0, 0.8, 1.0), &mut message_display);
         }
     }
 }
@@ -0,0 +1,241 @@
+use bevy::prelude::*;
+use avian3d::prelude::*;
+use crate::components::{Protagonist, Inventory};
+use crate::systems::core::interaction::{Interactable, InteractEvent};
+use crate::systems::core::screenplay::{MessageDisplay, display_message};
+use crate::systems::player::inventory::ItemCatalog;
+
+// Door timing
+const DOOR_OPEN_TIME: f32 = 1.5;  // Seconds to slide fully open or closed
+const DOOR_AUTO_CLOSE_TIME: f32 = 6.0;
+const DOOR_INTERACT_RANGE_MULTIPLIER: f32 = 1.5;  // Interact range relative to door width
+const DOOR_PASSAGE_RANGE_MULTIPLIER: f32 = 0.5;  // How close to the doorway counts as stepping through
+
+// Walls built around a doorway
+const DOOR_WALL_THICKNESS: f32 = 4.0;
+const DOOR_WALL_SINK: f32 = 10.0;  // How far the wall reaches below the threshold, to meet uneven ground
+
+#[derive(Clone)]
+pub struct DoorConfig {
+    pub position: Vec3,  // Bottom center of the door
+    pub rotation: Quat,  // Door faces its local -Z
+    pub size: Vec3,  // Width, height, thickness
+    pub texture: &'static str,
+    pub keycard: Option<&'static str>,  // Item id required to open
+    pub cycle_time: Option<f32>,  // Delay before opening, e.g. an airlock pressurizing
+    pub passage: Option<Vec3>,  // Where stepping through the open doorway leads
+}
+
+impl Default for DoorConfig {
+    fn default() -> Self {
+        Self {
+            position: Vec3::ZERO,
+            rotation: Quat::IDENTITY,
+            size: Vec3::new(16.0, 16.0, 1.0),
+            texture: "textures/airlock_door.png",
+            keycard: None,
+            cycle_time: None,
+            passage: None,
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct Door {
+    pub closed_position: Vec3,
+    pub open_offset: Vec3,
+    pub openness: f32,  // 0 closed, 1 open
+    pub is_open: bool,
+    pub keycard: Option<String>,
+    pub cycle_time: Option<f32>,
+    pub cycle: Option<Timer>,
+    pub passage: Option<Vec3>,
+    pub passage_range: f32,
+    pub auto_close: Timer,
+}
+
+impl Door {
+    pub fn is_cycling(&self) -> bool {
+        self.cycle.is_some()
+    }
+
+    fn open(&mut self) {
+        self.is_open = true;
+        self.auto_close.reset();
+    }
+}
+
+pub fn spawn_door(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<StandardMaterial>>,
+    asset_server: &Res<AssetServer>,
+    config: DoorConfig,
+) -> Entity {
+    let closed_position = config.position + Vec3::new(0.0, config.size.y / 2.0, 0.0);
+
+    commands.spawn((
+        RigidBody::Kinematic,
+        Collider::cuboid(config.size.x, config.size.y, config.size.z),
+        PbrBundle {
+            mesh: meshes.add(Cuboid::new(config.size.x, config.size.y, config.size.z)),
+            material: materials.add(StandardMaterial {
+                base_color_texture: Some(asset_server.load(config.texture)),
+                metallic: 1.0,
+                perceptual_roughness: 0.2,
+                emissive: Color::srgb(0.1, 0.0, 0.0).into(),
+                ..default()
+            }),
+            transform: Transform::from_translation(closed_position).with_rotation(config.rotation),
+            ..default()
+        },
+        Door {
+            closed_position,
+            open_offset: Vec3::Y * config.size.y,  // Slides up into the frame
+            openness: 0.0,
+            is_open: false,
+            keycard: config.keycard.map(str::to_string),
+            cycle_time: config.cycle_time,
+            cycle: None,
+            passage: config.passage,
+            passage_range: config.size.x * DOOR_PASSAGE_RANGE_MULTIPLIER,
+            auto_close: Timer::from_seconds(DOOR_AUTO_CLOSE_TIME, TimerMode::Once),
+        },
+        Interactable::new("open the door", config.size.x * DOOR_INTERACT_RANGE_MULTIPLIER),
+        Name::new("Door"),
+    )).id()
+}
+
+// A door set into a wall of `wall_size` (width, height above the threshold), centered on
+// the doorway. The wall takes the door's position and rotation; returns the door.
+pub fn spawn_door_in_wall(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<StandardMaterial>>,
+    asset_server: &Res<AssetServer>,
+    config: DoorConfig,
+    wall_size: Vec2,
+    wall_material: Handle<StandardMaterial>,
+) -> Entity {
+    let jamb_width = (wall_size.x - config.size.x) / 2.0;
+    let jamb_offset = (config.size.x + jamb_width) / 2.0;
+    let pieces = [  // Center relative to the threshold, size
+        (Vec3::new(-jamb_offset, (wall_size.y - DOOR_WALL_SINK) / 2.0, 0.0), Vec3::new(jamb_width, wall_size.y + DOOR_WALL_SINK, DOOR_WALL_THICKNESS)),
+        (Vec3::new(jamb_offset, (wall_size.y - DOOR_WALL_SINK) / 2.0, 0.0), Vec3::new(jamb_width, wall_size.y + DOOR_WALL_SINK, DOOR_WALL_THICKNESS)),
+        (Vec3::new(0.0, (wall_size.y + config.size.y) / 2.0, 0.0), Vec3::new(config.size.x, wall_size.y - config.size.y, DOOR_WALL_THICKNESS)),  // Lintel
+    ];
+
+    for (offset, size) in pieces {
+        commands.spawn((
+            RigidBody::Static,
+            Collider::cuboid(size.x, size.y, size.z),
+            PbrBundle {
+                mesh: meshes.add(Cuboid::new(size.x, size.y, size.z)),
+                material: wall_material.clone(),
+                transform: Transform::from_translation(config.position + config.rotation * offset)
+                    .with_rotation(config.rotation),
+                ..default()
+            },
+            Name::new("DoorWall"),
+        ));
+    }
+
+    spawn_door(commands, meshes, materials, asset_server, config)
+}
+
+pub fn handle_door_interaction(
+    mut interact_events: EventReader<InteractEvent>,
+    mut door_query: Query<&mut Door>,
+    inventory_query: Query<&Inventory, With<Protagonist>>,
+    catalog: Res<ItemCatalog>,
+    mut message_display: ResMut<MessageDisplay>,
+) {
+    for event in interact_events.read() {
+        let Ok(mut door) = door_query.get_mut(event.target) else {
+            continue;
+        };
+
+        if door.is_open {
+            door.is_open = false;
+            continue;
+        }
+        if door.is_cycling() {
+            continue;
+        }
+
+        if let Some(keycard) = &door.keycard {
+            let has_keycard = inventory_query
+                .get_single()
+                .map_or(false, |inventory| inventory.count(keycard) > 0);
+            if !has_keycard {
+                let name = catalog.get(keycard).map_or(keycard.as_str(), |item| item.name.as_str());
+                display_message(format!("REQUIRES {}", name), Color::srgb(1.0, 0.0, 0.0), &mut message_display);
+                continue;
+            }
+        }
+
+        match door.cycle_time {
+            Some(cycle_time) => {
+                door.cycle = Some(Timer::from_seconds(cycle_time, TimerMode::Once));
+                display_message("CYCLING...", Color::srgb(1.0, 0.5, 0.0), &mut message_display);
+            }
+            None => door.open(),
+        }
+    }
+}
+
+pub fn animate_doors(
+    time: Res<Time>,
+    mut door_query: Query<(&mut Door, &mut Transform, &mut Interactable)>,
+) {
+    for (mut door, mut transform, mut interactable) in door_query.iter_mut() {
+        let cycle_finished = door.cycle.as_mut().map_or(false, |cycle| cycle.tick(time.delta()).finished());
+        if cycle_finished {
+            door.cycle = None;
+            door.open();
+        }
+
+        if door.is_open && door.auto_close.tick(time.delta()).finished() {
+            door.is_open = false;
+        }
+
+        let target = if door.is_open { 1.0 } else { 0.0 };
+        let step = time.delta_seconds() / DOOR_OPEN_TIME;
+        door.openness += (target - door.openness).clamp(-step, step);
+
+        // Ease the slide so doors settle into place
+        let eased = door.openness * door.openness * (3.0 - 2.0 * door.openness);
+        transform.translation = door.closed_position + door.open_offset * eased;
+
+        let prompt = if door.is_open { "close the door" } else { "open the door" };
+        if interactable.prompt != prompt {
+            interactable.prompt = prompt.to_string();
+        }
+    }
+}
+
+// Stepping into a fully open doorway with a passage moves the protagonist through it
+pub fn pass_through_doors(
+    mut door_query: Query<&mut Door>,
+    mut protagonist_query: Query<(&mut Transform, &mut LinearVelocity), With<Protagonist>>,
+) {
+    let Ok((mut transform, mut velocity)) = protagonist_query.get_single_mut() else {
+        return;
+    };
+
+    for mut door in door_query.iter_mut() {
+        let Some(passage) = door.passage else {
+            continue;
+        };
+        if door.openness < 1.0 {
+            continue;
+        }
+
+        let offset = transform.translation - door.closed_position;
+        if offset.with_y(0.0).length() < door.passage_range {
+            transform.translation = passage;
+            velocity.0 = Vec3::ZERO;
+            door.is_open = false;
+        }
+    }
+}
@@ -1,8 +1,11 @@
 use bevy::prelude::*;
 use avian3d::prelude::*;
-use crate::components::{Tank, Protagonist};
-use crate::systems::player::driving::set_driving_state;
+use crate::components::{Tank, Protagonist, Health, Fuel};
+use crate::systems::player::driving::spawn_tank;
 use crate::systems::core::screenplay::{MessageDisplay, display_message};
+use crate::systems::core::interaction::InteractEvent;
+use crate::systems::environments::surface::SurfaceMaterial;
+use crate::systems::environments::doors::{spawn_door_in_wall, DoorConfig};
 
 // Constants for the garage structure
 pub const GARAGE_POSITION_1: Vec3 = Vec3::new(1800.4492, 2.6249862, -707.7545); // Near protagonist position
@@ -14,9 +17,12 @@ const ROOF_THICKNESS: f32 = 12.0;
 const PILLAR_WIDTH: f32 = 24.0;
 const LIGHT_PANEL_THICKNESS: f32 = 4.0;
 const LIGHT_PANEL_OFFSET: f32 = 12.0;
+const SERVICE_DOOR_SIZE: Vec3 = Vec3::new(20.0, 30.0, 1.0);  // Personnel door in the back wall
 
 // Add new constant for trigger volume
 const TRIGGER_VOLUME_SIZE: Vec3 = Vec3::new(600.0, 100.0, 600.0);
+const GARAGE_REPAIR_RATE: f32 = 5.0;  // Hull per second while parked inside
+const GARAGE_REFUEL_RATE: f32 = 10.0;  // Fuel per second while parked inside
 
 // Add new component for ring lights
 #[derive(Component)]
@@ -47,6 +53,7 @@ pub fn spawn_garage(
         },
         RigidBody::Static,
         Collider::cuboid(ROOF_WIDTH/2.0, ROOF_THICKNESS/2.0, ROOF_LENGTH/2.0),
+        SurfaceMaterial::Metal,
     ));
 
     // Fix pillar positions - remove the double position addition
@@ -72,9 +79,32 @@ pub fn spawn_garage(
             },
             RigidBody::Static,
             Collider::cuboid(PILLAR_WIDTH/2.0, ROOF_HEIGHT/2.0, PILLAR_WIDTH/2.0),
+            SurfaceMaterial::Metal,
         ));
     }
 
+    // Back wall between the rear pillars, with a service door; the tank leaves by the open front
+    let wall_material = materials.add(StandardMaterial {
+        base_color_texture: Some(metal_texture.clone()),
+        perceptual_roughness: 0.9,
+        metallic: 0.1,
+        ..default()
+    });
+    spawn_door_in_wall(
+        commands,
+        meshes,
+        materials,
+        asset_server,
+        DoorConfig {
+            position: position + Vec3::new(0.0, 0.0, -ROOF_LENGTH/2.0 + PILLAR_WIDTH/2.0),
+            size: SERVICE_DOOR_SIZE,
+            texture: "textures/rusty_metal_02_diff_4k.png",
+            ..default()
+        },
+        Vec2::new(ROOF_WIDTH - 2.0 * PILLAR_WIDTH, ROOF_HEIGHT - ROOF_THICKNESS/2.0),
+        wall_material,
+    );
+
     // Spawn thinner emissive white light panel
     commands.spawn(PbrBundle {
         mesh: meshes.add(Cuboid::new(ROOF_WIDTH - 8.0, LIGHT_PANEL_THICKNESS, ROOF_LENGTH - 8.0)),
@@ -103,21 +133,13 @@ pub fn spawn_garage(
         ..default()
     });
 
-    // Spawn the tank model
-    commands.spawn((
-        SceneBundle {
-            scene: asset_server.load("models/KB03-apc.glb#Scene0"),
-            transform: Transform::from_translation(position + Vec3::new(0.0, 0.0, 0.0))
-                .with_rotation(Quat::from_rotation_y(std::f32::consts::FRAC_PI_2))  // 90-degree rotation
-                .with_scale(Vec3::splat(6.0)),
-            ..default()
-        },
-        Tank,
-        Sensor,
-        RigidBody::Static,
-        Collider::cuboid(30.0, 30.0, 40.0),  // Reduced collider size
-        Name::new("Tank"),
-    ));
+    // Spawn the tank, parked under the roof until someone drives it off
+    spawn_tank(
+        commands,
+        asset_server,
+        Transform::from_translation(position + Vec3::new(0.0, 4.0, 0.0))
+            .with_rotation(Quat::from_rotation_y(std::f32::consts::FRAC_PI_2)),  // 90-degree rotation
+    );
 
     // Add point lights for enhanced lighting effect
     let light_positions = [
@@ -201,60 +223,36 @@ pub fn spawn_garages(
     spawn_garage(&mut commands, &mut meshes, &mut materials, &asset_server, GARAGE_POSITION_2);
 }
 
+// Point the player at their next objective when they take a tank from a garage
 pub fn handle_tank_interaction(
     mut commands: Commands,
-    mut collision_events: EventReader<CollisionStarted>,
-    tank_query: Query<(Entity, &Transform), With<Tank>>,
-    mut protagonist_query: Query<(Entity, &mut Protagonist, &mut Handle<Scene>)>,
-    children_query: Query<&Children>,
+    mut interact_events: EventReader<InteractEvent>,
+    tank_query: Query<(&Transform, &Tank)>,
     ring_lights_query: Query<Entity, With<GarageRingLight>>,
-    asset_server: Res<AssetServer>,
     mut message_display: ResMut<MessageDisplay>,
-    time: Res<Time>,
-    transform_query: Query<&Transform>,
 ) {
-    if time.elapsed_seconds() < 1.0 {
-        collision_events.clear();
-        return;
-    }
+    for event in interact_events.read() {
+        let Ok((tank_transform, tank)) = tank_query.get(event.target) else {
+            continue;
+        };
+        if tank.driver.is_some() {
+            continue;
+        }
 
-    for CollisionStarted(e1, e2) in collision_events.read() {
-        if let Some((tank_entity, tank_transform)) = tank_query.iter().next() {
-            if *e1 == tank_entity || *e2 == tank_entity {
-                if let Ok((protagonist_entity, mut protagonist, mut scene)) = protagonist_query.get_single_mut() {
-                    info!("Tank interaction triggered!");
-                    
-                    // Set driving state first, before despawning the tank
-                    protagonist.is_outside = true;
-                    set_driving_state(
-                        &mut protagonist,
-                        &mut scene,
-                        &asset_server,
-                        true,
-                        &mut commands,
-                        protagonist_entity,
-                        &children_query,
-                    );
-                    
-                    // Determine which garage we're in based on position
-                    let message = if tank_transform.translation.distance(GARAGE_POSITION_1) < 100.0 {
-                        "FIND THE AQUIFER ENTRY"
-                    } else {
-                        "FIND THE CONSTRUCTION SITE"
-                    };
-                    
-                    // Display the appropriate message
-                    display_message(message, Color::srgb(0.01, 0.55, 0.99), &mut message_display);
-                    
-                    // Despawn the tank after setting driving state
-                    commands.entity(tank_entity).despawn_recursive();
-                    
-                    // Despawn ring lights
-                    for ring_light in ring_lights_query.iter() {
-                        commands.entity(ring_light).despawn_recursive();
-                    }
-                }
-            }
+        // Determine which garage we're in based on position
+        let message = if tank_transform.translation.distance(GARAGE_POSITION_1) < 100.0 {
+            "FIND THE AQUIFER ENTRY"
+        } else if tank_transform.translation.distance(GARAGE_POSITION_2) < 100.0 {
+            "FIND THE CONSTRUCTION SITE"
+        } else {
+            continue;
+        };
+
+        display_message(message, Color::srgb(0.01, 0.55, 0.99), &mut message_display);
+
+        // Despawn ring lights
+        for ring_light in ring_lights_query.iter() {
+            commands.entity(ring_light).despawn_recursive();
         }
     }
 }
@@ -284,3 +282,27 @@ pub fn handle_garage_approach(
         }
     }
 }
+
+// Tanks inside a garage's trigger volume are repaired and refueled over time
+pub fn service_tanks_in_garages(
+    mut tank_query: Query<(&Transform, &mut Health, &mut Fuel), With<Tank>>,
+    mut message_display: ResMut<MessageDisplay>,
+    time: Res<Time>,
+) {
+    for (transform, mut hull, mut fuel) in tank_query.iter_mut() {
+        let in_garage = [GARAGE_POSITION_1, GARAGE_POSITION_2].iter().any(|garage| {
+            let offset = (transform.translation - *garage).abs();
+            offset.cmple(TRIGGER_VOLUME_SIZE / 2.0).all()
+        });
+        if !in_garage || (hull.current >= hull.max && fuel.current >= fuel.max) {
+            continue;
+        }
+
+        hull.current = (hull.current + GARAGE_REPAIR_RATE * time.delta_seconds()).min(hull.max);
+        fuel.current = (fuel.current + GARAGE_REFUEL_RATE * time.delta_seconds()).min(fuel.max);
+
+        if hull.current >= hull.max && fuel.current >= fuel.max {
+            display_message("TANK REPAIRED AND REFUELED", Color::srgb(0.01, 0.55, 0.99), &mut message_display);
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use bevy::prelude::*;
 use avian3d::prelude::*;
+use crate::systems::environments::doors::{spawn_door_in_wall, DoorConfig};
 
 pub const GEOTHERMAL_BASE_HEIGHT: f32 = 500.0;
 pub const GEOTHERMAL_BASE_RADIUS: f32 = 200.0;
@@ -14,6 +15,11 @@ pub const BRIDGE_HEIGHT: f32 = 20.0;
 pub const BRIDGE_WIDTH: f32 = 40.0;
 pub const BRIDGE_POSITION: Vec3 = Vec3::new(-200.0, 789.98, -200.0);
 
+pub const PUMP_HOUSE_POSITION: Vec3 = Vec3::new(-400.0, 0.0, -160.0);  // Against the base, door facing away
+pub const PUMP_HOUSE_SIZE: Vec3 = Vec3::new(60.0, 30.0, 40.0);
+const PUMP_HOUSE_WALL_THICKNESS: f32 = 4.0;
+const PUMP_HOUSE_DOOR_SIZE: Vec3 = Vec3::new(16.0, 24.0, 1.0);
+
 pub fn spawn_geothermal(
     commands: &mut Commands,
     meshes: &mut ResMut<Assets<Mesh>>,
@@ -96,4 +102,60 @@ pub fn spawn_geothermal(
         ColliderConstructorHierarchy::new(ColliderConstructor::TrimeshFromMesh),
         RigidBody::Static,
     ));
+    // Pump house at the foot of the base
+    let concrete = materials.add(StandardMaterial {
+        base_color_texture: Some(asset_server.load("textures/concrete.png")),
+        perceptual_roughness: 0.9,
+        metallic: 0.1,
+        ..default()
+    });
+    let (width, height, depth) = (PUMP_HOUSE_SIZE.x, PUMP_HOUSE_SIZE.y, PUMP_HOUSE_SIZE.z);
+    let walls = [  // Center relative to the floor, size
+        (Vec3::new(0.0, height / 2.0, -depth / 2.0), Vec3::new(width, height, PUMP_HOUSE_WALL_THICKNESS)),  // Back
+        (Vec3::new(-width / 2.0, height / 2.0, 0.0), Vec3::new(PUMP_HOUSE_WALL_THICKNESS, height, depth)),  // Left
+        (Vec3::new(width / 2.0, height / 2.0, 0.0), Vec3::new(PUMP_HOUSE_WALL_THICKNESS, height, depth)),  // Right
+        (Vec3::new(0.0, height, 0.0), Vec3::new(width, PUMP_HOUSE_WALL_THICKNESS, depth)),  // Roof
+    ];
+    for (offset, size) in walls {
+        commands.spawn((
+            RigidBody::Static,
+            Collider::cuboid(size.x, size.y, size.z),
+            Friction::new(10.0),
+            PbrBundle {
+                mesh: meshes.add(Cuboid::new(size.x, size.y, size.z)),
+                material: concrete.clone(),
+                transform: Transform::from_translation(PUMP_HOUSE_POSITION + offset),
+                ..default()
+            },
+            Name::new("PumpHouse"),
+        ));
+    }
+
+    spawn_door_in_wall(
+        commands,
+        meshes,
+        materials,
+        asset_server,
+        DoorConfig {
+            position: PUMP_HOUSE_POSITION + Vec3::new(0.0, 0.0, depth / 2.0),
+            size: PUMP_HOUSE_DOOR_SIZE,
+            ..default()
+        },
+        Vec2::new(width, height),
+        concrete,
+    );
+
+    commands.spawn((
+        PointLightBundle {
+            point_light: PointLight {
+                color: Color::srgb(1.0, 0.5, 0.1),
+                intensity: 200000.0,
+                range: depth * 2.0,
+                ..default()
+            },
+            transform: Transform::from_translation(PUMP_HOUSE_POSITION + Vec3::new(0.0, height - 4.0, 0.0)),
+            ..default()
+        },
+        Name::new("PumpHouseLight"),
+    ));
 }
\ No newline at end of file
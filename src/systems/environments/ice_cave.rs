@@ -1,10 +1,16 @@
 use bevy::prelude::*;
+use bevy::render::mesh::{Indices, PrimitiveTopology, VertexAttributeValues};
+use bevy::render::primitives::Aabb;
+use bevy::render::render_asset::RenderAssetUsages;
 use std::f32::consts::PI;
 use avian3d::prelude::*;
 use fastrand;
 use crate::components::Protagonist;
-use crate::systems::player::driving::set_driving_state;
+use crate::systems::player::driving::ExitTankEvent;
 use crate::systems::core::screenplay::{MessageDisplay, display_message};
+use crate::systems::environments::lighting::{LightingStack, LightingZone};
+use crate::systems::environments::wind::WindField;
+use crate::systems::environments::surface::SurfaceMaterial;
 
 // Cave dimensions
 const CAVE_POSITION_X: f32 = 2394.7814;
@@ -27,7 +33,7 @@ const LIGHT_COLOR_G: f32 = 0.95;
 const LIGHT_COLOR_B: f32 = 1.0;
 
 // Particle settings
-const PARTICLE_COUNT: usize = 30000;
+const PARTICLE_COUNT: usize = 30000;  // Default budget
 const PARTICLE_SIZE: f32 = 4.0;
 const PARTICLE_MIN_SPEED: f32 = 0.5;
 const PARTICLE_MAX_SPEED: f32 = 1.5;
@@ -38,13 +44,14 @@ const PARTICLE_EMISSIVE_STRENGTH: f32 = 0.08;
 const PARTICLE_LIFETIME: f32 = 20.0;
 const PARTICLE_ROOT_COUNT: i32 = 12;  // Number of root points for particle spawning
 const PARTICLE_ROOT_SPREAD: f32 = 30.0;  // Spread radius for root particles
+const PARTICLE_WIND_FACTOR: f32 = 0.15;  // The cave walls shelter the particles from most of the wind
+const PARTICLE_SIMULATION_RANGE: f32 = 3000.0;  // Particles freeze while the protagonist is further away than this
 
 pub fn spawn_ice_cave(
     commands: &mut Commands,
     meshes: &mut ResMut<Assets<Mesh>>,
     materials: &mut ResMut<Assets<StandardMaterial>>,
     asset_server: &Res<AssetServer>,
-    time: &Res<Time>,
 ) {
     let position = Vec3::new(CAVE_POSITION_X, CAVE_POSITION_Y, CAVE_POSITION_Z);
     
@@ -80,6 +87,7 @@ pub fn spawn_ice_cave(
         },
         RigidBody::Static,
         ColliderConstructor::TrimeshFromMesh,
+        SurfaceMaterial::Ice,
     ));
 
     // Replace spotlights with point lights
@@ -171,25 +179,40 @@ pub fn spawn_ice_cave(
             });
         }
     }
+}
 
-    // Spawn particles with multiple root points
-    let particle_material = materials.add(StandardMaterial {
-        base_color: PARTICLE_BASE_COLOR.with_alpha(0.6),
-        emissive: Color::srgb(
-            PARTICLE_BASE_COLOR.to_linear().red * PARTICLE_EMISSIVE_STRENGTH,
-            PARTICLE_BASE_COLOR.to_linear().green * PARTICLE_EMISSIVE_STRENGTH,
-            PARTICLE_BASE_COLOR.to_linear().blue * PARTICLE_EMISSIVE_STRENGTH,
-        ).into(),
-        alpha_mode: AlphaMode::Blend,
-        unlit: true,
-        ..default()
-    });
+// How many ice particles the cave runs, and how big they are
+#[derive(Resource, Clone, Copy)]
+pub struct IceParticleSettings {
+    pub budget: usize,
+    pub size: f32,
+}
 
-    let particle_mesh = meshes.add(Mesh::from(Sphere { radius: PARTICLE_SIZE }));
+impl Default for IceParticleSettings {
+    fn default() -> Self {
+        Self {
+            budget: PARTICLE_COUNT,
+            size: PARTICLE_SIZE,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct IceParticle {
+    offset: Vec3,  // From the cave origin
+    start_time: f32,
+}
 
-    for _ in 0..PARTICLE_COUNT {
-        let random_speed = PARTICLE_MIN_SPEED + fastrand::f32() * (PARTICLE_MAX_SPEED - PARTICLE_MIN_SPEED);
-        let root_offset = if fastrand::f32() < (PARTICLE_ROOT_COUNT as f32 / PARTICLE_COUNT as f32) {
+// Every particle in the cave, simulated here and drawn as one mesh
+#[derive(Component)]
+pub struct IceCaveParticles {
+    origin: Vec3,
+    particles: Vec<IceParticle>,
+}
+
+impl IceParticle {
+    fn new(budget: usize, now: f32) -> Self {
+        let offset = if fastrand::f32() < (PARTICLE_ROOT_COUNT as f32 / budget.max(1) as f32) {
             // Create root particle spread
             let angle = fastrand::f32() * PI * 2.0;
             let radius = fastrand::f32() * PARTICLE_ROOT_SPREAD;
@@ -197,133 +220,224 @@ pub fn spawn_ice_cave(
         } else {
             Vec3::ZERO
         };
-        
-        commands.spawn((
-            PbrBundle {
-                mesh: particle_mesh.clone(),
-                material: particle_material.clone(),
-                transform: Transform::from_translation(position + root_offset),
-                ..default()
-            },
-            IceCaveParticle {
-                velocity: Vec3::new(0.0, random_speed, 0.0),
-                origin: position,
-                start_time: time.elapsed_seconds(),
-            },
-        ));
+        Self { offset, start_time: now }
     }
 }
 
-// Add this component
-#[derive(Component)]
-pub struct IceCaveParticle {
-    velocity: Vec3,
-    origin: Vec3,
-    start_time: f32,
+impl IceCaveParticles {
+    // Grow or shrink to the budget
+    fn resize(&mut self, budget: usize, now: f32) {
+        if budget > self.particles.len() {
+            self.particles.extend((self.particles.len()..budget).map(|_| IceParticle::new(budget, now)));
+        } else {
+            self.particles.truncate(budget);
+        }
+    }
+
+    pub fn any_within(&self, position: Vec3, radius: f32) -> bool {
+        let local = position - self.origin;
+        self.particles.iter().any(|particle| particle.offset.distance_squared(local) < radius * radius)
+    }
+}
+
+// One octahedron per particle
+const PARTICLE_CORNERS: [Vec3; 6] = [Vec3::X, Vec3::NEG_X, Vec3::Y, Vec3::NEG_Y, Vec3::Z, Vec3::NEG_Z];
+const PARTICLE_FACES: [[u32; 3]; 8] = [
+    [0, 2, 4], [0, 5, 2], [0, 4, 3], [0, 3, 5],
+    [1, 4, 2], [1, 2, 5], [1, 3, 4], [1, 5, 3],
+];
+
+fn particle_indices(count: usize) -> Indices {
+    Indices::U32(
+        (0..count as u32)
+            .flat_map(|particle| PARTICLE_FACES.iter().flatten().map(move |&corner| particle * 6 + corner))
+            .collect()
+    )
+}
+
+// Give the mesh room for `count` particles. Normals never change, so they are only written here.
+fn allocate_particle_mesh(mesh: &mut Mesh, count: usize) {
+    let normals: Vec<[f32; 3]> = (0..count).flat_map(|_| PARTICLE_CORNERS.map(|corner| corner.to_array())).collect();
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, vec![[0.0f32; 3]; count * 6]);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, vec![[0.0f32; 4]; count * 6]);
+    mesh.insert_indices(particle_indices(count));
+}
+
+// The mesh changes every frame, so cull against everywhere the particles can go
+fn particle_bounds(size: f32) -> Aabb {
+    Aabb::from_min_max(
+        Vec3::new(-PARTICLE_MAX_RADIUS - size, -size, -PARTICLE_MAX_RADIUS - size),
+        Vec3::new(PARTICLE_MAX_RADIUS + size, PARTICLE_MAX_HEIGHT + size, PARTICLE_MAX_RADIUS + size),
+    )
 }
 
-// Add this system to your main.rs
+pub fn spawn_ice_particles(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    settings: Res<IceParticleSettings>,
+    time: Res<Time>,
+) {
+    let position = Vec3::new(CAVE_POSITION_X, CAVE_POSITION_Y, CAVE_POSITION_Z);
+
+    // Vertex colors carry each particle's fade, so one material covers them all
+    let particle_material = materials.add(StandardMaterial {
+        base_color: Color::WHITE,
+        alpha_mode: AlphaMode::Blend,
+        unlit: true,
+        ..default()
+    });
+
+    let mut particles = IceCaveParticles {
+        origin: position,
+        particles: Vec::new(),
+    };
+    particles.resize(settings.budget, time.elapsed_seconds());
+
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList, RenderAssetUsages::default());
+    allocate_particle_mesh(&mut mesh, settings.budget);
+
+    commands.spawn((
+        PbrBundle {
+            mesh: meshes.add(mesh),
+            material: particle_material,
+            transform: Transform::from_translation(position),
+            ..default()
+        },
+        particle_bounds(settings.size),
+        particles,
+    ));
+}
+
+// Move every particle and rewrite the shared mesh in place. Nothing is simulated while
+// the cave is out of view or far from the protagonist.
 pub fn update_ice_particles(
     time: Res<Time>,
-    mut query: Query<(Entity, &mut Transform, &mut IceCaveParticle, &Handle<StandardMaterial>)>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
+    wind: Res<WindField>,
+    settings: Res<IceParticleSettings>,
+    protagonist_query: Query<&Transform, With<Protagonist>>,
+    mut query: Query<(&mut IceCaveParticles, &Handle<Mesh>, &mut Aabb, &ViewVisibility)>,
+    mut meshes: ResMut<Assets<Mesh>>,
 ) {
-    for (_, mut transform, mut particle, material_handle) in query.iter_mut() {
-        let effect_age = time.elapsed_seconds() - particle.start_time;
-        let lifetime_fraction = (effect_age / PARTICLE_LIFETIME).clamp(0.0, 1.0);
-        
-        let height = transform.translation.y - particle.origin.y;
-        let height_fraction = (height / PARTICLE_MAX_HEIGHT).clamp(0.0, 1.0);
+    let Ok(protagonist_transform) = protagonist_query.get_single() else {
+        return;
+    };
 
-        // Motion calculations remain the same
-        let pos = transform.translation - particle.origin;
-        
-        let vertical_speed = (PARTICLE_MIN_SPEED + 
-            (fastrand::f32() * (PARTICLE_MAX_SPEED - PARTICLE_MIN_SPEED))) * 
-            (1.0 - height_fraction.powf(2.0));
-        
-        let flow = Vec3::new(
-            (time.elapsed_seconds() * 0.1 + pos.z * 0.01).sin() * 2.0,
-            0.0,
-            (time.elapsed_seconds() * 0.15 + pos.x * 0.01).cos() * 2.0
-        );
-
-        let medium_turb = Vec3::new(
-            (time.elapsed_seconds() * 0.3 + pos.y * 0.02).sin() * 1.0,
-            (time.elapsed_seconds() * 0.25 + pos.x * 0.02).cos() * 0.5,
-            (time.elapsed_seconds() * 0.35 + pos.z * 0.02).sin() * 1.0
-        );
-
-        let noise = Vec3::new(
-            (time.elapsed_seconds() * 2.0 + pos.y * 0.1).sin() * 0.2,
-            (time.elapsed_seconds() * 1.8 + pos.z * 0.1).cos() * 0.2,
-            (time.elapsed_seconds() * 1.9 + pos.x * 0.1).sin() * 0.2
-        );
-
-        let angle = time.elapsed_seconds() * 0.1 + height * 0.01;
-        let radius = PARTICLE_MAX_RADIUS * (1.0 - height_fraction.powf(0.5));
-        let drift = Vec3::new(
-            angle.cos() * radius * 0.01,
-            0.0,
-            angle.sin() * radius * 0.01
-        );
-
-        transform.translation += 
-            Vec3::Y * vertical_speed * time.delta_seconds() +
-            flow * time.delta_seconds() +
-            medium_turb * time.delta_seconds() +
-            noise * time.delta_seconds() +
-            drift * time.delta_seconds();
+    // One sample for the whole cave keeps this cheap with so many particles
+    let cave_wind = wind.sample(Vec3::new(CAVE_POSITION_X, CAVE_POSITION_Y, CAVE_POSITION_Z)) * PARTICLE_WIND_FACTOR;
+    let elapsed = time.elapsed_seconds();
+    let delta = time.delta_seconds();
 
-        let horizontal_distance = Vec2::new(
-            transform.translation.x - particle.origin.x,
-            transform.translation.z - particle.origin.z
-        ).length();
+    for (mut cave_particles, mesh_handle, mut bounds, view_visibility) in query.iter_mut() {
+        if settings.is_changed() {
+            *bounds = particle_bounds(settings.size);
+        }
+
+        let in_range = cave_particles.origin.distance(protagonist_transform.translation) < PARTICLE_SIMULATION_RANGE;
+        if !in_range || !view_visibility.get() {
+            continue;
+        }
+
+        let Some(mesh) = meshes.get_mut(mesh_handle) else {
+            continue;
+        };
+
+        if cave_particles.particles.len() != settings.budget {
+            cave_particles.resize(settings.budget, elapsed);
+            allocate_particle_mesh(mesh, settings.budget);
+        }
+
+        // Take the buffers out so positions and colors can be written together
+        let (
+            Some(VertexAttributeValues::Float32x3(mut positions)),
+            Some(VertexAttributeValues::Float32x4(mut colors)),
+        ) = (
+            mesh.remove_attribute(Mesh::ATTRIBUTE_POSITION),
+            mesh.remove_attribute(Mesh::ATTRIBUTE_COLOR),
+        ) else {
+            allocate_particle_mesh(mesh, cave_particles.particles.len());
+            continue;
+        };
+
+        for (index, particle) in cave_particles.particles.iter_mut().enumerate() {
+            let effect_age = elapsed - particle.start_time;
+            let lifetime_fraction = (effect_age / PARTICLE_LIFETIME).clamp(0.0, 1.0);
+
+            let pos = particle.offset;
+            let height = pos.y;
+            let height_fraction = (height / PARTICLE_MAX_HEIGHT).clamp(0.0, 1.0);
+
+            let vertical_speed = (PARTICLE_MIN_SPEED + 
+                (fastrand::f32() * (PARTICLE_MAX_SPEED - PARTICLE_MIN_SPEED))) * 
+                (1.0 - height_fraction.powf(2.0));
 
-        if lifetime_fraction >= 1.0 || 
-           height > PARTICLE_MAX_HEIGHT || 
-           horizontal_distance > PARTICLE_MAX_RADIUS {
-            let random_angle = fastrand::f32() * PI * 2.0;
-            let random_radius = fastrand::f32() * PARTICLE_MAX_RADIUS * 0.5;
-            transform.translation = particle.origin + Vec3::new(
-                random_angle.cos() * random_radius,
+            // Carried by the wind, more so the higher they float
+            let flow = cave_wind * (0.5 + height_fraction);
+
+            let medium_turb = Vec3::new(
+                (elapsed * 0.3 + pos.y * 0.02).sin() * 1.0,
+                (elapsed * 0.25 + pos.x * 0.02).cos() * 0.5,
+                (elapsed * 0.35 + pos.z * 0.02).sin() * 1.0
+            );
+
+            let noise = Vec3::new(
+                (elapsed * 2.0 + pos.y * 0.1).sin() * 0.2,
+                (elapsed * 1.8 + pos.z * 0.1).cos() * 0.2,
+                (elapsed * 1.9 + pos.x * 0.1).sin() * 0.2
+            );
+
+            // Spiral round the cave as they rise
+            let angle = elapsed * 0.1 + height * 0.01;
+            let radius = PARTICLE_MAX_RADIUS * (1.0 - height_fraction.powf(0.5));
+            let drift = Vec3::new(
+                angle.cos() * radius * 0.01,
                 0.0,
-                random_angle.sin() * random_radius
+                angle.sin() * radius * 0.01
             );
-            particle.start_time = time.elapsed_seconds();
-        }
 
-        // Simplified material updates
-        if let Some(material) = materials.get_mut(material_handle) {
+            particle.offset += 
+                (Vec3::Y * vertical_speed + flow + medium_turb + noise + drift) * delta;
+
+            let horizontal_distance = Vec2::new(particle.offset.x, particle.offset.z).length();
+            if lifetime_fraction >= 1.0 || 
+               particle.offset.y > PARTICLE_MAX_HEIGHT || 
+               horizontal_distance > PARTICLE_MAX_RADIUS {
+                let random_angle = fastrand::f32() * PI * 2.0;
+                let random_radius = fastrand::f32() * PARTICLE_MAX_RADIUS * 0.5;
+                particle.offset = Vec3::new(
+                    random_angle.cos() * random_radius,
+                    0.0,
+                    random_angle.sin() * random_radius
+                );
+                particle.start_time = elapsed;
+            }
+
             let fade = (1.0 - lifetime_fraction) * (1.0 - height_fraction);
-            
-            material.base_color = PARTICLE_BASE_COLOR.with_alpha(0.3 * fade);
-            material.emissive = (PARTICLE_BASE_COLOR.to_linear() * (PARTICLE_EMISSIVE_STRENGTH * fade)).into();
+            let color = PARTICLE_BASE_COLOR.to_linear();
+            let glow = 1.0 + PARTICLE_EMISSIVE_STRENGTH * fade;
+            for (vertex, corner) in PARTICLE_CORNERS.iter().enumerate() {
+                positions[index * 6 + vertex] = (particle.offset + *corner * settings.size).to_array();
+                colors[index * 6 + vertex] = [color.red * glow, color.green * glow, color.blue * glow, 0.3 * fade];
+            }
         }
+
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, colors);
     }
 }
 
 // Add this system
 pub fn handle_ice_cave_interactions(
-    mut query_set: ParamSet<(
-        Query<(Entity, &mut Transform, &mut Protagonist, &mut Handle<Scene>)>,
-        Query<&Transform, With<IceCaveParticle>>,
-    )>,
-    mut commands: Commands,
-    children_query: Query<&Children>,
-    asset_server: Res<AssetServer>,
+    mut protagonist_query: Query<(&mut Transform, &Protagonist)>,
+    particle_query: Query<&IceCaveParticles>,
+    mut exit_events: EventWriter<ExitTankEvent>,
     mut message_display: ResMut<MessageDisplay>,
+    mut lighting: ResMut<LightingStack>,
 ) {
     let cave_pos = Vec3::new(CAVE_POSITION_X, CAVE_POSITION_Y, CAVE_POSITION_Z);
-    
-    // Get particle positions and protagonist transform first
-    let particle_positions: Vec<Vec3> = query_set.p1()
-        .iter()
-        .map(|transform| transform.translation)
-        .collect();
-
-    // Then handle protagonist
-    if let Ok((entity, mut transform, mut protagonist, mut scene)) = query_set.p0().get_single_mut() {
+
+    if let Ok((mut transform, protagonist)) = protagonist_query.get_single_mut() {
         let horizontal_distance = Vec2::new(
             transform.translation.x - cave_pos.x,
             transform.translation.z - cave_pos.z
@@ -331,47 +445,19 @@ pub fn handle_ice_cave_interactions(
         
         let vertical_distance = (transform.translation.y - cave_pos.y).abs();
         if horizontal_distance < CAVE_RADIUS && vertical_distance < CAVE_HEIGHT / 2.0 {
+            lighting.push(LightingZone::Cave, 1.0);
             if protagonist.is_driving {
-                set_driving_state(
-                    &mut protagonist,
-                    &mut scene,
-                    &asset_server,
-                    false,
-                    &mut commands,
-                    entity,
-                    &children_query,
-                );
+                exit_events.send(ExitTankEvent);
                 display_message("FIND THE ACQUIFIER", Color::srgb(0.0, 0.2, 1.0), &mut message_display);
             }
+        } else {
+            lighting.pop(LightingZone::Cave);
         }
 
         // Check particle collisions
-        for particle_pos in particle_positions {
-            let distance = transform.translation.distance(particle_pos);
-            if distance < 5.0 {
-                transform.translation.y -= 30.0;
-                display_message("Find the Reactor and plant a charge (C key)", Color::rgb(1.0, 0.5, 0.0), &mut message_display);
-                break;
-            }
+        if particle_query.iter().any(|particles| particles.any_within(transform.translation, 5.0)) {
+            transform.translation.y -= 30.0;
+            display_message("Find the Reactor and plant a charge (C key)", Color::rgb(1.0, 0.5, 0.0), &mut message_display);
         }
     }
 }
-
-pub fn check_tank_exit(
-    mut protagonist_query: Query<(Entity, &mut Protagonist, &mut Handle<Scene>)>,
-    mut commands: Commands,
-    children_query: Query<&Children>,
-    asset_server: Res<AssetServer>,
-) {
-    if let Ok((entity, mut protagonist, mut scene)) = protagonist_query.get_single_mut() {
-        set_driving_state(
-            &mut protagonist,
-            &mut scene,
-            &asset_server,
-            false,
-            &mut commands,
-            entity,
-            &children_query,
-        );
-    }
-}
@@ -1,5 +1,7 @@
 use bevy::prelude::*;
 use std::f32::consts::PI;
+use crate::systems::environments::time_of_day::TimeOfDay;
+use crate::systems::environments::wind::WindField;
 
 const LANTERN_POSITIONS: &[Vec3] = &[
     Vec3::new(-252.9185, 3.992052, -101.97985),
@@ -26,8 +28,8 @@ const FLOAT_HEIGHT: f32 = 30.0;
 const FLOAT_SPEED: f32 = 0.8;
 const LANTERN_RADIUS: f32 = 8.0;
 const LIGHT_INTENSITY: f32 = 150000.0;
-const WIND_STRENGTH: f32 = 8.0;
-const WIND_SPEED: f32 = 0.4;
+const WIND_SWAY: f32 = 0.6;  // Sway distance per unit of wind speed
+const LIGHT_FADE_SPEED: f32 = 0.5;  // How quickly lanterns come on at dusk and go out in the morning
 
 #[derive(Component)]
 pub struct FloatingLantern {
@@ -35,6 +37,9 @@ pub struct FloatingLantern {
     time_offset: f32,
 }
 
+#[derive(Component)]
+pub struct LanternLight;
+
 pub fn spawn_lanterns(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
@@ -65,22 +70,26 @@ pub fn spawn_lanterns(
             },
         ))
         .with_children(|parent| {
-            parent.spawn(PointLightBundle {
-                point_light: PointLight {
-                    color: Color::rgb(1.0, 0.6, 0.2),
-                    intensity: LIGHT_INTENSITY,
-                    radius: LANTERN_RADIUS * 2.0,
-                    shadows_enabled: true,
+            parent.spawn((
+                PointLightBundle {
+                    point_light: PointLight {
+                        color: Color::rgb(1.0, 0.6, 0.2),
+                        intensity: LIGHT_INTENSITY,
+                        radius: LANTERN_RADIUS * 2.0,
+                        shadows_enabled: true,
+                        ..default()
+                    },
                     ..default()
                 },
-                ..default()
-            });
+                LanternLight,
+            ));
         });
     }
 }
 
 pub fn update_lanterns(
     time: Res<Time>,
+    wind: Res<WindField>,
     mut query: Query<(&mut Transform, &FloatingLantern)>,
 ) {
     for (mut transform, lantern) in query.iter_mut() {
@@ -89,9 +98,10 @@ pub fn update_lanterns(
         // Vertical floating motion
         let height_offset = (elapsed * FLOAT_SPEED).sin() * 5.0;
         
-        // Enhanced wind effect
-        let wind_x = (elapsed * WIND_SPEED).sin() * WIND_STRENGTH;
-        let wind_z = (elapsed * WIND_SPEED * 0.7).cos() * WIND_STRENGTH;
+        // Lean downwind
+        let sway = wind.sample(lantern.origin) * WIND_SWAY;
+        let wind_x = sway.x;
+        let wind_z = sway.z;
         
         // Additional turbulence
         let turbulence_x = (elapsed * 1.2 + lantern.time_offset).sin() * 2.0;
@@ -109,3 +119,19 @@ pub fn update_lanterns(
         transform.rotation = Quat::from_euler(EulerRot::XYZ, tilt_angle_x, 0.0, tilt_angle_z);
     }
 }
+
+// Lanterns light up at dusk and go out in the morning
+pub fn update_lantern_lights(
+    time: Res<Time>,
+    time_of_day: Res<TimeOfDay>,
+    mut light_query: Query<&mut PointLight, With<LanternLight>>,
+    mut level: Local<Option<f32>>,
+) {
+    let target = time_of_day.daylight().lanterns;
+    let current = level.get_or_insert(target);
+    *current += (target - *current) * (time.delta_seconds() * LIGHT_FADE_SPEED).min(1.0);
+
+    for mut light in light_query.iter_mut() {
+        light.intensity = LIGHT_INTENSITY * *current;
+    }
+}
@@ -0,0 +1,179 @@
+use bevy::prelude::*;
+use crate::systems::environments::time_of_day::{Daylight, Sun, TimeOfDay};
+
+// Blending
+const LIGHTING_BLEND_SPEED: f32 = 2.0;  // How quickly a zone fades in or out, per second
+
+// Ambient levels
+const OUTSIDE_AMBIENT: f32 = 400.0;
+const INDOORS_AMBIENT: f32 = 200.0;  // Under cover
+const CAVE_AMBIENT: f32 = 150.0;
+const CAVE_AMBIENT_COLOR: Color = Color::srgb(0.3, 0.45, 0.7);
+const CAVE_SUN_FACTOR: f32 = 0.2;  // Light that gets in through the cave mouth
+const UNDERWATER_AMBIENT: f32 = 100.0;
+const UNDERWATER_AMBIENT_COLOR: Color = Color::srgb(0.0, 0.2, 0.4);
+const UNDERWATER_SUN_COLOR: Color = Color::srgb(0.2, 0.4, 0.6);
+const UNDERWATER_SUN_FACTOR: f32 = 0.3;
+
+// Alarm lighting
+const ALARM_AMBIENT: f32 = 300.0;
+const ALARM_AMBIENT_COLOR: Color = Color::srgb(0.4, 0.0, 0.0);
+const ALARM_ILLUMINANCE: f32 = 1000.0;
+const ALARM_COLOR: Color = Color::srgb(1.0, 0.0, 0.0);
+const ALARM_PULSE_SPEED: f32 = 4.0;
+
+// Places and situations that change the lighting. Later zones sit higher in the
+// stack and win over earlier ones.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LightingZone {
+    Outside,  // The base layer, lit by the time of day
+    Indoors,
+    Cave,
+    Underwater,
+    Alarm,
+}
+
+const LIGHTING_ZONES: [LightingZone; 5] = [
+    LightingZone::Outside,
+    LightingZone::Indoors,
+    LightingZone::Cave,
+    LightingZone::Underwater,
+    LightingZone::Alarm,
+];
+
+// Ambient and sun light for one zone
+#[derive(Clone, Copy, Debug)]
+pub struct LightingProfile {
+    pub ambient_color: Color,
+    pub ambient_brightness: f32,
+    pub sun_color: Color,
+    pub sun_illuminance: f32,
+}
+
+impl LightingProfile {
+    fn lerp(self, other: Self, t: f32) -> Self {
+        Self {
+            ambient_color: self.ambient_color.mix(&other.ambient_color, t),
+            ambient_brightness: self.ambient_brightness + (other.ambient_brightness - self.ambient_brightness) * t,
+            sun_color: self.sun_color.mix(&other.sun_color, t),
+            sun_illuminance: self.sun_illuminance + (other.sun_illuminance - self.sun_illuminance) * t,
+        }
+    }
+}
+
+impl LightingZone {
+    pub fn profile(self, daylight: &Daylight, elapsed: f32) -> LightingProfile {
+        match self {
+            LightingZone::Outside => LightingProfile {
+                ambient_color: daylight.ambient_color,
+                ambient_brightness: OUTSIDE_AMBIENT * daylight.ambient_brightness,
+                sun_color: daylight.sun_color,
+                sun_illuminance: daylight.sun_illuminance,
+            },
+            LightingZone::Indoors => LightingProfile {
+                ambient_color: daylight.ambient_color,
+                ambient_brightness: INDOORS_AMBIENT * daylight.ambient_brightness,
+                sun_color: daylight.sun_color,
+                sun_illuminance: daylight.sun_illuminance,
+            },
+            LightingZone::Cave => LightingProfile {
+                ambient_color: CAVE_AMBIENT_COLOR,
+                ambient_brightness: CAVE_AMBIENT,
+                sun_color: daylight.sun_color,
+                sun_illuminance: daylight.sun_illuminance * CAVE_SUN_FACTOR,
+            },
+            LightingZone::Underwater => LightingProfile {
+                ambient_color: UNDERWATER_AMBIENT_COLOR,
+                ambient_brightness: UNDERWATER_AMBIENT,
+                sun_color: UNDERWATER_SUN_COLOR,
+                sun_illuminance: daylight.sun_illuminance * UNDERWATER_SUN_FACTOR,
+            },
+            LightingZone::Alarm => {
+                let pulse = 0.5 + 0.5 * (elapsed * ALARM_PULSE_SPEED).sin();
+                LightingProfile {
+                    ambient_color: ALARM_AMBIENT_COLOR,
+                    ambient_brightness: ALARM_AMBIENT,
+                    sun_color: ALARM_COLOR,
+                    sun_illuminance: ALARM_ILLUMINANCE * pulse,
+                }
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct LightingLayer {
+    zone: LightingZone,
+    target: f32,  // Weight the zone was pushed with, 0 once popped
+    weight: f32,  // Eases toward the target
+}
+
+// Zones the protagonist is in, blended bottom to top into the lights
+#[derive(Resource)]
+pub struct LightingStack {
+    layers: Vec<LightingLayer>,
+}
+
+impl Default for LightingStack {
+    fn default() -> Self {
+        Self {
+            layers: LIGHTING_ZONES.iter()
+                .map(|&zone| {
+                    let weight = if zone == LightingZone::Outside { 1.0 } else { 0.0 };
+                    LightingLayer { zone, target: weight, weight }
+                })
+                .collect(),
+        }
+    }
+}
+
+impl LightingStack {
+    fn layer_mut(&mut self, zone: LightingZone) -> &mut LightingLayer {
+        self.layers.iter_mut().find(|layer| layer.zone == zone).unwrap()
+    }
+
+    // Fade a zone in, 1 to fully take over from the zones below it
+    pub fn push(&mut self, zone: LightingZone, weight: f32) {
+        self.layer_mut(zone).target = weight.clamp(0.0, 1.0);
+    }
+
+    // Fade a zone back out
+    pub fn pop(&mut self, zone: LightingZone) {
+        if zone != LightingZone::Outside {
+            self.layer_mut(zone).target = 0.0;
+        }
+    }
+}
+
+// The only system that writes the ambient light and the sun's color and brightness
+pub fn apply_lighting(
+    mut stack: ResMut<LightingStack>,
+    time_of_day: Res<TimeOfDay>,
+    mut ambient_light: ResMut<AmbientLight>,
+    mut sun_query: Query<&mut DirectionalLight, With<Sun>>,
+    time: Res<Time>,
+) {
+    let step = (time.delta_seconds() * LIGHTING_BLEND_SPEED).min(1.0);
+    for layer in stack.layers.iter_mut() {
+        layer.weight += (layer.target - layer.weight) * step;
+    }
+
+    let daylight = time_of_day.daylight();
+    let elapsed = time.elapsed_seconds();
+    let profile = stack.layers.iter()
+        .skip(1)
+        .fold(LightingZone::Outside.profile(&daylight, elapsed), |profile, layer| {
+            if layer.weight > 0.0 {
+                profile.lerp(layer.zone.profile(&daylight, elapsed), layer.weight)
+            } else {
+                profile
+            }
+        });
+
+    ambient_light.color = profile.ambient_color;
+    ambient_light.brightness = profile.ambient_brightness;
+    for mut light in sun_query.iter_mut() {
+        light.color = profile.sun_color;
+        light.illuminance = profile.sun_illuminance;
+    }
+}
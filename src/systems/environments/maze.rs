@@ -1,10 +1,15 @@
 use bevy::prelude::*;
 use avian3d::prelude::*;
+use petgraph::algo::astar;
+use petgraph::graph::{NodeIndex, UnGraph};
+use petgraph::unionfind::UnionFind;
+use rand::{rngs::StdRng, Rng, SeedableRng};
 use crate::components::Protagonist;
-use crate::systems::player::dirigible::DirigibleBalloon;
+use crate::systems::player::dirigible::attach_balloon;
 use crate::systems::core::screenplay::{MessageDisplay, display_message};
 use crate::systems::environments::ladder::{spawn_ladder, LadderConfig};
-use crate::systems::player::driving::set_driving_state;
+use crate::systems::player::driving::ExitTankEvent;
+use crate::systems::core::interaction::{Interactable, InteractEvent};
 
 // Maze configuration
 pub const MAZE_POSITION: Vec3 = Vec3::new(
@@ -19,6 +24,8 @@ const MAZE_SIZE: usize = 20;
 
 // Add new constants for sphere and arrows
 const SPHERE_RADIUS: f32 = 20.0;
+const DIRIGIBLE_INTERACT_RANGE: f32 = SPHERE_RADIUS * 4.0;  // The sphere floats 50 units above the path
+const TANK_EXIT_RADIUS: f32 = 20.0;  // How close to the ladder the tank parks itself
 
 // Add new constants
 const PYLON_WIDTH: f32 = 60.0;
@@ -27,8 +34,17 @@ const NEON_STRIP_WIDTH: f32 = 4.0;
 const NEON_STRIP_HEIGHT: f32 = 1.0;
 
 // Add new constants
-const HEIGHT_DROP_PER_SEGMENT: f32 = 20.0;  // How much each segment drops
+const HEIGHT_DROP_PER_SEGMENT: f32 = 20.0;  // How much each segment drops on the first level
 const MIN_HEIGHT: f32 = 100.0;  // Minimum height before stopping descent
+const MAZE_STEP_HEIGHT: f32 = HEIGHT_DROP_PER_SEGMENT;  // Tallest step between platforms that can be walked
+
+// Hints
+const HINT_KEY: KeyCode = KeyCode::KeyN;
+const HINT_DURATION: f32 = 20.0;  // Seconds the arrows stay up
+const HINT_RANGE: f32 = CELL_SIZE;  // How far from the walkway a hint can be asked for
+const HINT_ARROW_RADIUS: f32 = 12.0;
+const HINT_ARROW_LENGTH: f32 = 40.0;
+const HINT_ARROW_LIFT: f32 = 30.0;  // Above the walkway
 
 // Update neon color constants
 const NEON_BLUE: Color = Color::rgba(0.0, 0.2, 0.99, 1.0);
@@ -38,110 +54,404 @@ const NEON_BLUE_EMISSION: Color = Color::rgb(0.0, 0.4, 0.8);
 #[derive(Component)]
 pub struct DirigibleTriggerZone {
     pub position: Vec3,
-    pub radius: f32,
     pub entity: Entity,
 }
 
-#[derive(Clone, Copy, PartialEq)]
-enum Cell {
-    Wall,
-    Path,
+// How the walkway is laid out
+#[derive(Clone, Copy, Debug)]
+pub enum MazeLayout {
+    // A turtle path: F steps one cell, + and - turn by `angle` degrees, [ and ] start and
+    // end a branch. A symbol with several rules picks one at random from the seed.
+    LSystem {
+        axiom: &'static str,
+        rules: &'static [(char, &'static str)],
+        iterations: usize,
+        angle: f32,
+    },
+    // A spanning tree over a `size` x `size` grid of cells, so every cell is reachable
+    // by exactly one route
+    Grid {
+        size: usize,
+        algorithm: GridAlgorithm,
+    },
 }
 
-// L-System rules
-const ITERATIONS: usize = 3;
-const ANGLE: f32 = 90.0;
+#[derive(Clone, Copy, Debug)]
+pub enum GridAlgorithm {
+    RecursiveBacktracker,  // Long winding corridors
+    Wilson,  // Unbiased, lots of short dead ends
+}
 
-const MAX_RADIUS: f32 = 5000.0;
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct MazeConfig {
+    pub layout: MazeLayout,
+    pub seed: u64,
+    pub footprint: f32,  // Radius the layout has to fit in
+    pub height_drop: f32,  // How far each step along the way descends
+}
 
-#[derive(Clone, Debug)]
-struct LSystemPath {
-    points: Vec<Vec3>,
-    direction: Vec3,
+// Environment variable naming the level to build, e.g. MAZE_LEVEL=level_2
+const MAZE_LEVEL_VAR: &str = "MAZE_LEVEL";
+
+// The level named by MAZE_LEVEL, or the first one
+impl Default for MazeConfig {
+    fn default() -> Self {
+        let Ok(name) = std::env::var(MAZE_LEVEL_VAR) else {
+            return MAZE_LEVELS[0].1;
+        };
+        MazeConfig::for_level(&name).unwrap_or_else(|| {
+            let names: Vec<&str> = MAZE_LEVELS.iter().map(|(name, _)| *name).collect();
+            warn!("Maze: unknown level '{}', expected one of {:?}", name, names);
+            MAZE_LEVELS[0].1
+        })
+    }
 }
 
-impl LSystemPath {
-    fn new() -> Self {
-        Self {
-            points: vec![Vec3::ZERO],
-            direction: Vec3::new(1.0, 0.0, 0.0),
+impl MazeConfig {
+    pub fn for_level(name: &str) -> Option<Self> {
+        MAZE_LEVELS.iter().find(|(level, _)| *level == name).map(|(_, config)| *config)
+    }
+}
+
+// Each level's maze, picked by name with MAZE_LEVEL
+pub const MAZE_LEVELS: &[(&str, MazeConfig)] = &[
+    ("level_0", MazeConfig {
+        layout: MazeLayout::LSystem {
+            axiom: "F",
+            rules: &[('F', "F+F-F-FF+F+F-F")],
+            iterations: 3,
+            angle: 90.0,
+        },
+        seed: 0,
+        footprint: 5000.0,
+        height_drop: HEIGHT_DROP_PER_SEGMENT,
+    }),
+    ("level_1", MazeConfig {
+        layout: MazeLayout::Grid {
+            size: MAZE_SIZE,
+            algorithm: GridAlgorithm::RecursiveBacktracker,
+        },
+        seed: 1,
+        footprint: 5000.0,
+        height_drop: 0.0,  // Flat, so dead ends can be walked back out of
+    }),
+    ("level_2", MazeConfig {
+        layout: MazeLayout::LSystem {
+            axiom: "F",
+            rules: &[('F', "F[+F]F[-F]F"), ('F', "F[+F]F"), ('F', "F[-F]FF")],
+            iterations: 3,
+            angle: 90.0,
+        },
+        seed: 7,
+        footprint: 4000.0,
+        height_drop: 10.0,
+    }),
+    ("level_3", MazeConfig {
+        layout: MazeLayout::Grid {
+            size: MAZE_SIZE / 2,
+            algorithm: GridAlgorithm::Wilson,
+        },
+        seed: 3,
+        footprint: 3000.0,
+        height_drop: 0.0,
+    }),
+];
+
+// One platform between two maze points
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct MazeSegment {
+    pub start: usize,
+    pub end: usize,
+    pub level: usize,  // Steps from the entrance, for the height drop
+}
+
+// A generated layout on the XZ plane, before it is placed in the world. Points are unique,
+// so paths that cross or retrace share them; a retraced segment is only kept if it sits
+// at a different level.
+#[derive(Clone, Debug, Default)]
+pub struct MazePlan {
+    pub points: Vec<Vec3>,
+    pub segments: Vec<MazeSegment>,
+    pub entrance: usize,
+}
+
+impl MazePlan {
+    fn point_index(&mut self, point: Vec3) -> usize {
+        let key = |p: &Vec3| (p.x.round() as i32, p.z.round() as i32);
+        if let Some(index) = self.points.iter().position(|p| key(p) == key(&point)) {
+            return index;
         }
+        self.points.push(point);
+        self.points.len() - 1
     }
 
-    fn add_point(&mut self) -> bool {
-        let last_point = *self.points.last().unwrap();
-        let new_point = last_point + self.direction * CELL_SIZE;
-        
-        // Check if new point would exceed maximum radius
-        if new_point.length() > MAX_RADIUS {
-            return false;  // Don't add point if it exceeds radius
+    // Where segment ends meet. Ends at the same point only join when their walkways are
+    // within a step of each other, so passing over or under another platform isn't a way
+    // on. Returns the joint at each segment's start and end, and where each joint is.
+    fn joints(&self, heights: &[f32]) -> (Vec<[usize; 2]>, Vec<Vec3>) {
+        let mut ends: Vec<(usize, f32, usize, usize)> = self.segments.iter()
+            .zip(heights)
+            .enumerate()
+            .flat_map(|(index, (segment, &height))| [(segment.start, height, index, 0), (segment.end, height, index, 1)])
+            .collect();
+        ends.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.total_cmp(&b.1)));
+
+        let mut segment_joints = vec![[0; 2]; self.segments.len()];
+        let mut positions: Vec<Vec3> = Vec::new();
+        let mut previous: Option<(usize, f32)> = None;
+        for (point, height, segment, end) in ends {
+            let joins = previous.is_some_and(|(previous_point, previous_height)| {
+                previous_point == point && height - previous_height <= MAZE_STEP_HEIGHT
+            });
+            match positions.last_mut() {
+                // A joint sits on the highest walkway that meets there
+                Some(position) if joins => position.y = height,
+                _ => positions.push(self.points[point].with_y(height)),
+            }
+            segment_joints[segment][end] = positions.len() - 1;
+            previous = Some((point, height));
         }
-        
-        self.points.push(new_point);
-        true
+
+        (segment_joints, positions)
     }
 
-    fn turn_left(&mut self) {
-        let angle = ANGLE.to_radians();
-        self.direction = Vec3::new(
-            self.direction.z,
-            0.0,
-            -self.direction.x
-        );
+    // Joint at the entrance, on the first segment out of it (where the ladder goes)
+    fn entrance_joint(&self, joints: &[[usize; 2]]) -> Option<usize> {
+        self.segments.iter()
+            .position(|segment| segment.start == self.entrance)
+            .map(|index| joints[index][0])
     }
 
-    fn turn_right(&mut self) {
-        let angle = -ANGLE.to_radians();
-        self.direction = Vec3::new(
-            -self.direction.z,
-            0.0,
-            self.direction.x
-        );
+    // Drop anything that can't be walked to from the entrance, returning how many
+    // segments went
+    fn keep_reachable(&mut self, segment_height: impl Fn(&MazeSegment) -> f32) -> usize {
+        let before = self.segments.len();
+        let heights: Vec<f32> = self.segments.iter().map(&segment_height).collect();
+        let (joints, positions) = self.joints(&heights);
+        let Some(entrance) = self.entrance_joint(&joints) else {
+            self.segments.clear();
+            return before;
+        };
+
+        let mut components = UnionFind::new(positions.len());
+        for &[start, end] in joints.iter() {
+            components.union(start, end);
+        }
+        let mut index = 0;
+        self.segments.retain(|_| {
+            let reachable = components.equiv(joints[index][0], entrance);
+            index += 1;
+            reachable
+        });
+        before - self.segments.len()
+    }
+
+    fn add_segment(&mut self, start: Vec3, end: Vec3, level: usize) {
+        let start = self.point_index(start);
+        let end = self.point_index(end);
+        let exists = self.segments.iter()
+            .filter(|s| s.level == level)
+            .any(|s| (s.start, s.end) == (start, end) || (s.start, s.end) == (end, start));
+        if start != end && !exists {
+            self.segments.push(MazeSegment { start, end, level });
+        }
+    }
+}
+
+pub fn generate_maze(config: &MazeConfig) -> MazePlan {
+    let mut rng = StdRng::seed_from_u64(config.seed);
+    match config.layout {
+        MazeLayout::LSystem { axiom, rules, iterations, angle } => {
+            generate_l_system(axiom, rules, iterations, angle, config.footprint, &mut rng)
+        }
+        MazeLayout::Grid { size, algorithm } => {
+            generate_grid(size, algorithm, config.footprint, &mut rng)
+        }
     }
 }
 
-fn generate_l_system(iterations: usize) -> LSystemPath {
-    let mut path = LSystemPath::new();
-    let mut commands = String::from("F");
+// Turtle state while walking the L-system string
+#[derive(Clone, Copy, Debug)]
+struct Turtle {
+    position: Vec3,
+    direction: Vec3,
+    level: usize,
+}
+
+impl Turtle {
+    // Step forward a cell unless that leaves the footprint
+    fn try_step(&mut self, footprint: f32) -> Option<Vec3> {
+        let next = self.position + self.direction * CELL_SIZE;
+        if next.length() > footprint {
+            return None;
+        }
+        Some(next)
+    }
+
+    fn turn(&mut self, degrees: f32) {
+        self.direction = (Quat::from_rotation_y(degrees.to_radians()) * self.direction).normalize();
+    }
+}
 
+fn generate_l_system(
+    axiom: &str,
+    rules: &[(char, &str)],
+    iterations: usize,
+    angle: f32,
+    footprint: f32,
+    rng: &mut StdRng,
+) -> MazePlan {
     // Apply L-system rules
+    let mut commands = String::from(axiom);
     for _ in 0..iterations {
         let mut new_commands = String::new();
         for c in commands.chars() {
-            match c {
-                'F' => new_commands.push_str("F+F-F-FF+F+F-F"),
-                '+' => new_commands.push('+'),
-                '-' => new_commands.push('-'),
-                _ => {}
+            let productions: Vec<&str> = rules.iter()
+                .filter(|(symbol, _)| *symbol == c)
+                .map(|(_, production)| *production)
+                .collect();
+            if productions.is_empty() {
+                new_commands.push(c);
+            } else {
+                new_commands.push_str(productions[rng.gen_range(0..productions.len())]);
             }
         }
         commands = new_commands;
     }
 
-    // Generate path with radius restriction
+    // Walk the path with radius restriction
+    let mut plan = MazePlan::default();
+    let mut turtle = Turtle {
+        position: Vec3::ZERO,
+        direction: Vec3::X,
+        level: 0,
+    };
+    let mut branches = Vec::new();
+    plan.entrance = plan.point_index(turtle.position);
+
     for c in commands.chars() {
         match c {
             'F' => {
-                if !path.add_point() {
-                    // If point wasn't added, try turning and adding again
-                    path.turn_right();
-                    if !path.add_point() {
-                        path.turn_left();
-                        path.turn_left();
-                        if !path.add_point() {
-                            // If all directions fail, stop generating
-                            break;
-                        }
+                // If the step leaves the footprint, try turning right, then left
+                let mut next = turtle.try_step(footprint);
+                for turn in [-angle, 2.0 * angle] {
+                    if next.is_some() {
+                        break;
                     }
+                    turtle.turn(turn);
+                    next = turtle.try_step(footprint);
+                }
+                let Some(next) = next else {
+                    // If all directions fail, stop generating
+                    break;
+                };
+                plan.add_segment(turtle.position, next, turtle.level);
+                turtle.position = next;
+                turtle.level += 1;
+            },
+            '+' => turtle.turn(angle),
+            '-' => turtle.turn(-angle),
+            '[' => branches.push(turtle),
+            ']' => {
+                if let Some(saved) = branches.pop() {
+                    turtle = saved;
                 }
             },
-            '+' => path.turn_left(),
-            '-' => path.turn_right(),
             _ => {}
         }
     }
 
-    path
+    plan
+}
+
+fn generate_grid(size: usize, algorithm: GridAlgorithm, footprint: f32, rng: &mut StdRng) -> MazePlan {
+    // Shrink the grid until its corners fit in the footprint
+    let max_size = (footprint * std::f32::consts::SQRT_2 / CELL_SIZE) as usize + 1;
+    let size = size.min(max_size).max(2);
+    let cell_count = size * size;
+
+    let neighbours = |cell: usize| {
+        let (x, z) = (cell % size, cell / size);
+        let mut result = Vec::with_capacity(4);
+        if x > 0 { result.push(cell - 1); }
+        if x + 1 < size { result.push(cell + 1); }
+        if z > 0 { result.push(cell - size); }
+        if z + 1 < size { result.push(cell + size); }
+        result
+    };
+
+    let mut passages = Vec::with_capacity(cell_count - 1);
+    match algorithm {
+        GridAlgorithm::RecursiveBacktracker => {
+            let mut visited = vec![false; cell_count];
+            let mut stack = vec![0];
+            visited[0] = true;
+            while let Some(&cell) = stack.last() {
+                let unvisited: Vec<usize> = neighbours(cell).into_iter().filter(|&n| !visited[n]).collect();
+                if unvisited.is_empty() {
+                    stack.pop();
+                    continue;
+                }
+                let next = unvisited[rng.gen_range(0..unvisited.len())];
+                visited[next] = true;
+                passages.push((cell, next));
+                stack.push(next);
+            }
+        }
+        GridAlgorithm::Wilson => {
+            // Loop-erased random walks from each cell until they hit the tree
+            let mut in_tree = vec![false; cell_count];
+            let mut next_step = vec![0; cell_count];
+            in_tree[rng.gen_range(0..cell_count)] = true;
+            for start in 0..cell_count {
+                let mut cell = start;
+                while !in_tree[cell] {
+                    let options = neighbours(cell);
+                    next_step[cell] = options[rng.gen_range(0..options.len())];
+                    cell = next_step[cell];
+                }
+                let mut cell = start;
+                while !in_tree[cell] {
+                    in_tree[cell] = true;
+                    passages.push((cell, next_step[cell]));
+                    cell = next_step[cell];
+                }
+            }
+        }
+    }
+
+    // Levels are steps from the entrance corner, found breadth first over the tree
+    let mut depth = vec![usize::MAX; cell_count];
+    depth[0] = 0;
+    let mut queue = std::collections::VecDeque::from([0]);
+    while let Some(cell) = queue.pop_front() {
+        for &(a, b) in passages.iter() {
+            let other = if a == cell { b } else if b == cell { a } else { continue };
+            if depth[other] == usize::MAX {
+                depth[other] = depth[cell] + 1;
+                queue.push_back(other);
+            }
+        }
+    }
+
+    // Walk passages outward from the entrance so the first segment starts there
+    passages.sort_by_key(|&(a, b)| depth[a].min(depth[b]));
+
+    let offset = (size - 1) as f32 / 2.0;
+    let cell_position = |cell: usize| Vec3::new(
+        ((cell % size) as f32 - offset) * CELL_SIZE,
+        0.0,
+        ((cell / size) as f32 - offset) * CELL_SIZE,
+    );
+
+    let mut plan = MazePlan::default();
+    plan.entrance = plan.point_index(cell_position(0));
+    for (a, b) in passages {
+        let (near, far) = if depth[a] <= depth[b] { (a, b) } else { (b, a) };
+        plan.add_segment(cell_position(near), cell_position(far), depth[near]);
+    }
+
+    plan
 }
 
 pub fn spawn_maze(
@@ -149,6 +459,7 @@ pub fn spawn_maze(
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
     asset_server: Res<AssetServer>,
+    config: Res<MazeConfig>,
 ) {
     let concrete_material = materials.add(StandardMaterial {
         base_color_texture: Some(asset_server.load("textures/ice_texture3.png")),
@@ -172,46 +483,57 @@ pub fn spawn_maze(
         ..default()
     });
 
-    // Generate L-system path
-    let mut path = generate_l_system(ITERATIONS);
-    
+    // Generate the layout for this level
+    let mut plan = generate_maze(&config);
+
     // Center and position path
-    let bounds = path.points.iter().fold(
+    let bounds = plan.points.iter().fold(
         (Vec3::splat(f32::MAX), Vec3::splat(f32::MIN)),
-        |(min, max), &p| (
-            Vec3::new(min.x.min(p.x), min.y.min(p.y), min.z.min(p.z)),
-            Vec3::new(max.x.max(p.x), max.y.max(p.y), max.z.max(p.z))
-        )
+        |(min, max), &p| (min.min(p), max.max(p))
     );
     let center = (bounds.0 + bounds.1) / 2.0;
-    
-    // Adjust points with descending height - ensure flat segments
-    let mut current_height = MAZE_POSITION.y;  // Start at maze position height
-    
-    // First flatten X-Z coordinates
-    for point in path.points.iter_mut() {
-        point.x -= center.x;
-        point.z -= center.z;
-        point.x += MAZE_POSITION.x;
-        point.z += MAZE_POSITION.z;
+    for point in plan.points.iter_mut() {
+        point.x += MAZE_POSITION.x - center.x;
+        point.z += MAZE_POSITION.z - center.z;
     }
 
-    // Then handle Y coordinates for flat segments
-    for i in 0..path.points.len()-1 {
-        path.points[i].y = current_height;
-        path.points[i+1].y = current_height;
-        current_height -= HEIGHT_DROP_PER_SEGMENT;
+    // Each segment is flat, descending with its distance from the entrance
+    let segment_height = |segment: &MazeSegment| MAZE_POSITION.y - segment.level as f32 * config.height_drop;
+
+    // The dirigible goes over the furthest segment that can be built. It is picked before
+    // the repair, so a layout that cuts it off gets caught.
+    plan.segments.retain(|segment| segment_height(segment) >= MIN_HEIGHT);
+    let Some(&intended_exit) = plan.segments.iter().max_by_key(|segment| segment.level) else {
+        return;
+    };
+
+    // Repair the layout: segments too low to build, or crossings at different heights,
+    // can cut off the ones beyond them
+    let unreachable = plan.keep_reachable(segment_height);
+    if unreachable > 0 {
+        warn!("Maze: dropped {} segments that can't be reached from the entrance", unreachable);
     }
+    if plan.segments.is_empty() {
+        return;
+    }
+    let exit = match plan.segments.iter().position(|segment| *segment == intended_exit) {
+        Some(exit) => exit,
+        None => {
+            warn!("Maze: the dirigible can't be reached from the entrance, moving it to the furthest reachable segment");
+            plan.segments.iter()
+                .enumerate()
+                .max_by_key(|(_, segment)| segment.level)
+                .map(|(index, _)| index)
+                .unwrap()
+        }
+    };
 
     // Spawn segments and pylons
-    for (i, window) in path.points.windows(2).enumerate() {
-        let start = window[0];
-        let end = window[1];
-        
-        if start.y < MIN_HEIGHT {
-            continue;
-        }
-        
+    for (i, segment) in plan.segments.iter().enumerate() {
+        let height = segment_height(segment);
+        let start = plan.points[segment.start].with_y(height);
+        let end = plan.points[segment.end].with_y(height);
+
         let direction = Vec3::new(
             end.x - start.x,
             0.0,  // Force horizontal direction
@@ -269,7 +591,7 @@ pub fn spawn_maze(
                 Collider::cuboid(PYLON_WIDTH, pylon_height, PYLON_WIDTH),
             ));
 
-            // Add ladder on the first pylon of the first segment
+            // Add ladder on the entrance pylon of the first segment
             if i == 0 && pylon_index == 0 {
                 let ladder_pos = Vec3::new(
                     inset_point.x - PYLON_WIDTH/2.0 - 1.0,  // Opposite side of inset pylon
@@ -298,18 +620,230 @@ pub fn spawn_maze(
         }
     }
 
-    // Find the last valid segment and spawn dirigible trigger
-    let last_valid_segment = path.points.windows(2)
-        .rev()  // Start from the end
-        .find(|window| window[0].y >= MIN_HEIGHT);  // Find first segment above min height
+    // Spawn the dirigible trigger over the exit segment
+    let last_segment = plan.segments[exit];
+    let height = segment_height(&last_segment);
+    let start = plan.points[last_segment.start].with_y(height);
+    let end = plan.points[last_segment.end].with_y(height);
+    let segment_center = start + (end - start) / 2.0;  // Calculate true center
+
+    // Spawn dirigible trigger well above the last segment
+    spawn_dirigible_trigger(&mut commands, &mut meshes, &mut materials, &asset_server, segment_center);
+
+    // Check the way through and report on it
+    let heights: Vec<f32> = plan.segments.iter().map(segment_height).collect();
+    let maze = MazeGraph::new(&plan, &heights, exit);
+    info!("{}", maze.stats());
+    commands.insert_resource(maze);
+}
 
-    if let Some(last_window) = last_valid_segment {
-        let start = last_window[0];
-        let end = last_window[1];
-        let segment_center = start + (end - start) / 2.0;  // Calculate true center
-        
-        // Spawn dirigible trigger well above the last segment
-        spawn_dirigible_trigger(&mut commands, &mut meshes, &mut materials, &asset_server, segment_center);
+// One platform in the maze graph
+#[derive(Clone, Copy, Debug)]
+pub struct MazeEdge {
+    pub length: f32,
+    pub height: f32,  // Walkway height
+}
+
+// The built maze as a graph. Nodes are the joints where platforms meet, at the height of
+// the highest walkway there; platforms crossing at different heights meet at separate joints.
+#[derive(Resource)]
+pub struct MazeGraph {
+    pub graph: UnGraph<Vec3, MazeEdge>,
+    pub entrance: NodeIndex,  // At the ladder
+    pub exit: NodeIndex,  // Under the dirigible
+}
+
+// Layout numbers for tuning maze configs
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MazeStats {
+    pub segments: usize,
+    pub length: f32,  // Total walkway
+    pub solution_length: Option<f32>,  // Shortest walk from the entrance to the exit
+    pub dead_ends: usize,  // Points with one way on, other than the entrance and exit
+    pub junctions: usize,  // Points with three or more ways on
+    pub branching: f32,  // Average extra ways on at a junction
+}
+
+impl std::fmt::Display for MazeStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Maze: {} segments, {:.0} units of walkway, solution {}, {} dead ends, {} junctions, branching {:.2}",
+            self.segments,
+            self.length,
+            self.solution_length.map_or("none".to_string(), |length| format!("{:.0}", length)),
+            self.dead_ends,
+            self.junctions,
+            self.branching,
+        )
+    }
+}
+
+impl MazeGraph {
+    // `exit` is the segment the dirigible is over, which is reached at its far end
+    fn new(plan: &MazePlan, heights: &[f32], exit: usize) -> Self {
+        let (joints, positions) = plan.joints(heights);
+        let mut graph = UnGraph::with_capacity(positions.len(), plan.segments.len());
+        for &position in positions.iter() {
+            graph.add_node(position);
+        }
+        for ((segment, &height), &[start, end]) in plan.segments.iter().zip(heights).zip(joints.iter()) {
+            let length = plan.points[segment.start].distance(plan.points[segment.end]);
+            graph.add_edge(NodeIndex::new(start), NodeIndex::new(end), MazeEdge { length, height });
+        }
+
+        Self {
+            graph,
+            entrance: NodeIndex::new(plan.entrance_joint(&joints).unwrap_or(0)),
+            exit: NodeIndex::new(joints[exit][1]),
+        }
+    }
+
+    // Shortest walk between two points and its length
+    pub fn shortest_path(&self, from: NodeIndex, to: NodeIndex) -> Option<(f32, Vec<NodeIndex>)> {
+        let goal = self.graph[to];
+        astar(
+            &self.graph,
+            from,
+            |node| node == to,
+            |edge| edge.weight().length,
+            |node| self.graph[node].distance(goal),
+        )
+    }
+
+    // Closest point on the walkway across, if one is within range. Where walkways cross
+    // at different heights, the one nearest in height wins.
+    pub fn nearest_node(&self, position: Vec3, range: f32) -> Option<NodeIndex> {
+        let flat = position.with_y(0.0);
+        self.graph.node_indices()
+            .filter(|&node| self.graph.neighbors(node).next().is_some())
+            .map(|node| {
+                let point = self.graph[node];
+                (node, point.with_y(0.0).distance(flat), (point.y - position.y).abs())
+            })
+            .filter(|(_, distance, _)| *distance <= range)
+            .min_by(|a, b| a.1.total_cmp(&b.1).then(a.2.total_cmp(&b.2)))
+            .map(|(node, _, _)| node)
+    }
+
+    // Ways on from a point; a retraced segment at another height doesn't count twice
+    fn ways_on(&self, node: NodeIndex) -> usize {
+        let mut neighbours: Vec<NodeIndex> = self.graph.neighbors(node).collect();
+        neighbours.sort();
+        neighbours.dedup();
+        neighbours.len()
+    }
+
+    pub fn stats(&self) -> MazeStats {
+        let mut stats = MazeStats {
+            segments: self.graph.edge_count(),
+            length: self.graph.edge_weights().map(|edge| edge.length).sum(),
+            solution_length: self.shortest_path(self.entrance, self.exit).map(|(length, _)| length),
+            ..default()
+        };
+
+        let mut extra_ways = 0;
+        for node in self.graph.node_indices() {
+            let ways = self.ways_on(node);
+            if ways == 1 && node != self.entrance && node != self.exit {
+                stats.dead_ends += 1;
+            } else if ways >= 3 {
+                stats.junctions += 1;
+                extra_ways += ways - 2;
+            }
+        }
+        if stats.junctions > 0 {
+            stats.branching = extra_ways as f32 / stats.junctions as f32;
+        }
+
+        stats
+    }
+}
+
+#[derive(Component)]
+pub struct MazeHintArrow {
+    pub timer: Timer,
+}
+
+// Point arrows along the shortest way from the protagonist to the dirigible
+pub fn request_maze_hint(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    maze: Option<Res<MazeGraph>>,
+    protagonist_query: Query<&Transform, With<Protagonist>>,
+    exit_query: Query<(), With<DirigibleTriggerZone>>,
+    arrow_query: Query<Entity, With<MazeHintArrow>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut arrow_assets: Local<Option<(Handle<Mesh>, Handle<StandardMaterial>)>>,
+    mut message_display: ResMut<MessageDisplay>,
+) {
+    if !keyboard.just_pressed(HINT_KEY) || exit_query.is_empty() {
+        return;
+    }
+    let (Some(maze), Ok(transform)) = (maze, protagonist_query.get_single()) else {
+        return;
+    };
+
+    let Some(start) = maze.nearest_node(transform.translation, HINT_RANGE) else {
+        display_message("GET CLOSER TO THE MAZE FOR A HINT", Color::WHITE, &mut message_display);
+        return;
+    };
+    let Some((_, path)) = maze.shortest_path(start, maze.exit) else {
+        display_message("NO WAY THROUGH FROM HERE", Color::srgb(1.0, 0.5, 0.0), &mut message_display);
+        return;
+    };
+
+    for entity in arrow_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    let (mesh, material) = arrow_assets.get_or_insert_with(|| (
+        meshes.add(Cone { radius: HINT_ARROW_RADIUS, height: HINT_ARROW_LENGTH }),
+        materials.add(StandardMaterial {
+            base_color: NEON_BLUE,
+            emissive: NEON_BLUE_EMISSION.to_linear() * 4.0,
+            unlit: true,
+            ..default()
+        }),
+    )).clone();
+
+    for window in path.windows(2) {
+        let Some(edge) = maze.graph.find_edge(window[0], window[1]) else {
+            continue;
+        };
+        let height = maze.graph[edge].height + WALL_HEIGHT / 2.0 + HINT_ARROW_LIFT;
+        let from = maze.graph[window[0]].with_y(height);
+        let to = maze.graph[window[1]].with_y(height);
+
+        commands.spawn((
+            PbrBundle {
+                mesh: mesh.clone(),
+                material: material.clone(),
+                transform: Transform::from_translation(from.lerp(to, 0.5))
+                    .with_rotation(Quat::from_rotation_arc(Vec3::Y, (to - from).normalize())),
+                ..default()
+            },
+            MazeHintArrow {
+                timer: Timer::from_seconds(HINT_DURATION, TimerMode::Once),
+            },
+        ));
+    }
+
+    display_message("FOLLOW THE ARROWS TO THE DIRIGIBLE", Color::WHITE, &mut message_display);
+}
+
+// Take the arrows down once they've been up a while or the dirigible is gone
+pub fn update_maze_hint_arrows(
+    mut commands: Commands,
+    mut arrow_query: Query<(Entity, &mut MazeHintArrow)>,
+    exit_query: Query<(), With<DirigibleTriggerZone>>,
+    time: Res<Time>,
+) {
+    for (entity, mut arrow) in arrow_query.iter_mut() {
+        if arrow.timer.tick(time.delta()).finished() || exit_query.is_empty() {
+            commands.entity(entity).despawn_recursive();
+        }
     }
 }
 
@@ -340,71 +874,55 @@ fn spawn_dirigible_trigger(
         },
         DirigibleTriggerZone {
             position: sphere_position,
-            radius: SPHERE_RADIUS,
             entity: trigger_entity,
         },
+        Interactable::new("board the dirigible", DIRIGIBLE_INTERACT_RANGE),
     ));
 }
 
 // Update the check_dirigible_trigger function
 pub fn check_dirigible_trigger(
-    trigger_query: Query<(Entity, &DirigibleTriggerZone)>,
-    mut player_query: Query<(Entity, &Transform, &mut Protagonist)>,
+    mut interact_events: EventReader<InteractEvent>,
+    trigger_query: Query<(), With<DirigibleTriggerZone>>,
+    mut player_query: Query<(Entity, &mut Protagonist)>,
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
     asset_server: Res<AssetServer>,
     mut message_display: ResMut<MessageDisplay>,
 ) {
-    if let (Ok((trigger_entity, trigger)), Ok((player_entity, player_transform, mut protagonist))) = (
-        trigger_query.get_single(),
-        player_query.get_single_mut()
-    ) {
-        let player_pos = player_transform.translation;
-        
-        let horizontal_dist = Vec2::new(
-            player_pos.x - trigger.position.x,
-            player_pos.z - trigger.position.z
-        ).length();
-
-        if horizontal_dist < trigger.radius * 1.5 && player_pos.y < trigger.position.y {
-            if !protagonist.is_dirigible {
-                // Display message using the same system as garage
-                display_message(
-                    "FIND THE LAUNCH SILO (SHIFT TO FLOAT)",
-                    Color::WHITE,
-                    &mut message_display
-                );
-
-                protagonist.is_dirigible = true;
-                protagonist.is_swimming = false;
-                protagonist.is_falling = false;
-                protagonist.is_climbing = false;
-
-                // Spawn the dirigible balloon
-                commands.entity(player_entity).with_children(|parent| {
-                    parent.spawn((
-                        PbrBundle {
-                            mesh: meshes.add(Mesh::from(Sphere::new(20.0))),
-                            material: materials.add(StandardMaterial {
-                                base_color: Color::srgb(1.0, 1.0, 1.0),
-                                base_color_texture: Some(asset_server.load("textures/american-flag-background.png")),
-                                metallic: 0.8,
-                                perceptual_roughness: 0.1,
-                                reflectance: 0.7,
-                                ..default()
-                            }),
-                            transform: Transform::from_xyz(0.0, 30.0, 0.0),
-                            ..default()
-                        },
-                        DirigibleBalloon,
-                    ));
-                });
+    for event in interact_events.read() {
+        let (Ok(()), Ok((player_entity, mut protagonist))) = (
+            trigger_query.get(event.target),
+            player_query.get_single_mut()
+        ) else {
+            continue;
+        };
 
-                // Despawn both the trigger entity and the sphere
-                commands.entity(trigger_entity).despawn_recursive();
-            }
+        if protagonist.is_dirigible {
+            continue;
         }
+
+        // Display message using the same system as garage
+        display_message(
+            "FIND THE LAUNCH SILO (SHIFT TO DROP BALLAST)",
+            Color::WHITE,
+            &mut message_display
+        );
+
+        attach_balloon(
+            &mut commands,
+            &mut meshes,
+            &mut materials,
+            &asset_server,
+            player_entity,
+            &mut protagonist,
+            20.0,
+            30.0,
+        );
+
+        // Despawn both the trigger entity and the sphere
+        commands.entity(event.target).despawn_recursive();
     }
 }
 
@@ -413,31 +931,90 @@ pub struct TankExitZone {
     pub position: Vec3,
 }
 
-// Replace tank exit system
+// Park the tank when it first rolls up to the ladder; the player can climb back in
 pub fn check_tank_exit(
-    mut protagonist_query: Query<(Entity, &Transform, &mut Protagonist, &mut Handle<Scene>)>,
-    transform_query: Query<&Transform>,
+    protagonist_query: Query<(&Transform, &Protagonist)>,
     tank_exit_query: Query<&TankExitZone>,
-    mut commands: Commands,
-    children_query: Query<&Children>,
-    asset_server: Res<AssetServer>,
+    mut was_in_zone: Local<bool>,
+    mut exit_events: EventWriter<ExitTankEvent>,
     mut message_display: ResMut<MessageDisplay>,
 ) {
-    if let (Ok((entity, transform, mut protagonist, mut scene)), Ok(exit_zone)) = (
-        protagonist_query.get_single_mut(),
+    if let (Ok((transform, protagonist)), Ok(exit_zone)) = (
+        protagonist_query.get_single(),
         tank_exit_query.get_single()
     ) {
-        if protagonist.is_driving && transform.translation.distance(exit_zone.position) < 20.0 {
-            set_driving_state(
-                &mut protagonist,
-                &mut scene,
-                &asset_server,
-                false,  // Set to false to exit tank
-                &mut commands,
-                entity,
-                &children_query,
-            );
-            display_message("CLIMB THE LADDER", Color::WHITE, &mut message_display);
+        let in_zone = transform.translation.distance(exit_zone.position) < TANK_EXIT_RADIUS;
+        if in_zone && !*was_in_zone && protagonist.is_driving {
+            exit_events.send(ExitTankEvent);
+            display_message("CLIMB THE LADDER - PRESS N FOR A HINT", Color::WHITE, &mut message_display);
         }
+        *was_in_zone = in_zone;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn small_grid(algorithm: GridAlgorithm) -> MazeConfig {
+        MazeConfig {
+            layout: MazeLayout::Grid { size: 4, algorithm },
+            seed: 5,
+            footprint: 5000.0,
+            height_drop: 0.0,
+        }
+    }
+
+    #[test]
+    fn small_grid_is_a_spanning_tree() {
+        for algorithm in [GridAlgorithm::RecursiveBacktracker, GridAlgorithm::Wilson] {
+            let mut plan = generate_maze(&small_grid(algorithm));
+            assert_eq!(plan.points.len(), 16);
+            assert_eq!(plan.segments.len(), 15);
+            assert_eq!(plan.keep_reachable(|_| MAZE_POSITION.y), 0);
+
+            let heights = vec![MAZE_POSITION.y; plan.segments.len()];
+            let exit = plan.segments.iter()
+                .enumerate()
+                .max_by_key(|(_, segment)| segment.level)
+                .map(|(index, _)| index)
+                .unwrap();
+            let maze = MazeGraph::new(&plan, &heights, exit);
+            let stats = maze.stats();
+
+            assert_eq!(stats.segments, 15);
+            assert!((stats.length - 15.0 * CELL_SIZE).abs() < 1e-3);
+            let solution = stats.solution_length.unwrap();
+            assert!(solution >= CELL_SIZE && solution <= stats.length);
+
+            // A tree has two leaves plus one for every extra way on at a junction
+            let leaves: Vec<NodeIndex> = maze.graph.node_indices().filter(|&node| maze.ways_on(node) == 1).collect();
+            let extra_ways = (stats.branching * stats.junctions as f32).round() as usize;
+            assert_eq!(leaves.len(), 2 + extra_ways);
+            let ends = leaves.iter().filter(|&&node| node == maze.entrance || node == maze.exit).count();
+            assert_eq!(stats.dead_ends, leaves.len() - ends);
+        }
+    }
+
+    #[test]
+    fn crossings_at_different_heights_are_not_connected() {
+        // Two walkways cross at the origin, one far above the other
+        let mut plan = MazePlan::default();
+        plan.entrance = plan.point_index(Vec3::new(-CELL_SIZE, 0.0, 0.0));
+        plan.add_segment(Vec3::new(-CELL_SIZE, 0.0, 0.0), Vec3::ZERO, 0);
+        plan.add_segment(Vec3::ZERO, Vec3::new(CELL_SIZE, 0.0, 0.0), 1);
+        plan.add_segment(Vec3::new(0.0, 0.0, -CELL_SIZE), Vec3::ZERO, 20);
+        plan.add_segment(Vec3::ZERO, Vec3::new(0.0, 0.0, CELL_SIZE), 21);
+        let height = |segment: &MazeSegment| MAZE_POSITION.y - segment.level as f32 * HEIGHT_DROP_PER_SEGMENT;
+
+        let heights: Vec<f32> = plan.segments.iter().map(height).collect();
+        let graph = MazeGraph::new(&plan, &heights, 3);
+        assert_eq!(graph.stats().solution_length, None);
+        let graph = MazeGraph::new(&plan, &heights, 1);
+        assert_eq!(graph.stats().solution_length, Some(2.0 * CELL_SIZE));
+
+        assert_eq!(plan.keep_reachable(height), 2);
+        assert_eq!(plan.segments.len(), 2);
+        assert!(plan.segments.iter().all(|segment| segment.level < 20));
     }
 }
@@ -12,4 +12,11 @@ pub mod glaciers;
 pub mod geothermal;
 pub mod acquifier;
 pub mod big_pipe;
-pub mod lanterns;
\ No newline at end of file
+pub mod lanterns;
+pub mod doors;
+pub mod wind;
+pub mod tram;
+pub mod surface;
+pub mod weather;
+pub mod time_of_day;
+pub mod lighting;
\ No newline at end of file
@@ -1,5 +1,17 @@
 use bevy::prelude::*;
 use avian3d::prelude::*;
+use crate::components::{Protagonist, Health, Inventory};
+use crate::systems::core::screenplay::{MessageDisplay, display_message};
+use crate::systems::core::sentry::{
+    spawn_explosion_effects,
+    ExplosionCounter,
+    ExplosionMaterials,
+    NoiseEvent,
+    SentrySurge,
+};
+use crate::systems::core::setup::PROTAGONIST_START;
+use crate::systems::environments::doors::{spawn_door, DoorConfig};
+use crate::systems::environments::lighting::{LightingStack, LightingZone};
 
 pub const WALL_THICKNESS: f32 = 20.0;
 const REACTOR_POSITION: Vec3 = Vec3::new(-455.0 * 2.0, 1.6, 915.0 * 2.0);
@@ -14,6 +26,51 @@ const DISC_GAP: f32 = 10.0;
 const DISC_THICKNESS: f32 = 2.0;
 const SAFETY_MARGIN: f32 = 5.0;
 
+// Maintenance door through the reactor wall
+const REACTOR_DOOR_SIZE: Vec3 = Vec3::new(20.0, 30.0, 1.0);
+const REACTOR_DOOR_EXIT_OFFSET: f32 = 40.0;  // How far from the wall the passage lets you out
+const REACTOR_KEYCARD: &str = "keycard_reactor";
+
+// Demolition charge constants
+const CHARGE_REACH: f32 = 12.0;  // How far in front of the protagonist a charge can be planted
+const CHARGE_SURFACE_MARGIN: f32 = 10.0;  // Slack around the reactor wall for a valid surface
+const CHARGE_SIZE: Vec3 = Vec3::new(1.5, 1.0, 0.6);
+const CHARGE_ARM_TIME: f32 = 20.0;
+const CHARGE_BLINK_INTENSITY: f32 = 500000.0;
+const CHARGE_NOISE_RADIUS: f32 = 5000.0;
+const DETONATION_EXPLOSIONS: usize = 6;  // Secondary blasts around the reactor wall
+
+// Escape sequence constants
+const ESCAPE_TIME: f32 = 120.0;
+const ESCAPE_SAFE_DISTANCE: f32 = 3000.0;
+const ALARM_COLOR: Color = Color::srgb(1.0, 0.0, 0.0);
+
+// Sent by the inventory when the protagonist uses a demolition charge
+#[derive(Event)]
+pub struct PlantChargeEvent {
+    pub item: String,
+}
+
+#[derive(Default)]
+pub enum SabotageState {
+    #[default]
+    Intact,
+    Armed(Timer),
+    Escape(Timer),
+    Complete,
+}
+
+#[derive(Resource, Default)]
+pub struct ReactorSabotage {
+    pub state: SabotageState,
+}
+
+#[derive(Component)]
+pub struct ReactorCharge;
+
+#[derive(Component)]
+pub struct SabotageCountdownText;
+
 pub fn spawn_reactor(
     commands: &mut Commands,
     meshes: &mut ResMut<Assets<Mesh>>,
@@ -90,6 +147,25 @@ pub fn spawn_reactor(
         ..default()
     });
 
+    // Keycard door into the reactor on the outer wall, and a plain one back out
+    let ground = REACTOR_POSITION.with_y(0.0);
+    spawn_door(commands, meshes, materials, asset_server, DoorConfig {
+        position: ground - Vec3::Z * (CYLINDER_RADIUS + REACTOR_DOOR_SIZE.z),
+        size: REACTOR_DOOR_SIZE,
+        texture: "textures/worn_corrugated_iron_diff_4k.png",
+        keycard: Some(REACTOR_KEYCARD),
+        passage: Some(ground + Vec3::new(0.0, 5.0, -(CYLINDER_INNER_RADIUS - REACTOR_DOOR_EXIT_OFFSET))),
+        ..default()
+    });
+    spawn_door(commands, meshes, materials, asset_server, DoorConfig {
+        position: ground - Vec3::Z * (CYLINDER_INNER_RADIUS - REACTOR_DOOR_SIZE.z),
+        rotation: Quat::from_rotation_y(std::f32::consts::PI),  // Face the reactor core
+        size: REACTOR_DOOR_SIZE,
+        texture: "textures/worn_corrugated_iron_diff_4k.png",
+        passage: Some(ground + Vec3::new(0.0, 5.0, -(CYLINDER_RADIUS + REACTOR_DOOR_EXIT_OFFSET))),
+        ..default()
+    });
+
     // Add intense point light in the lower half of the reactor
     commands.spawn(PointLightBundle {
         point_light: PointLight {
@@ -106,3 +182,220 @@ pub fn spawn_reactor(
     });
 }
 
+
+// A charge must go on the reactor wall, inside or out
+fn is_reactor_surface(point: Vec3) -> bool {
+    let radial = Vec2::new(point.x - REACTOR_POSITION.x, point.z - REACTOR_POSITION.z).length();
+    let height = point.y - REACTOR_POSITION.y;
+    radial > CYLINDER_INNER_RADIUS - CHARGE_SURFACE_MARGIN
+        && radial < CYLINDER_RADIUS + CHARGE_SURFACE_MARGIN
+        && height.abs() < CYLINDER_HEIGHT / 2.0
+}
+
+pub fn setup_sabotage_hud(mut commands: Commands) {
+    commands.spawn((
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font_size: 36.0,
+                color: ALARM_COLOR,
+                ..default()
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(70.0),
+            right: Val::Px(20.0),
+            ..default()
+        }),
+        SabotageCountdownText,
+    ));
+}
+
+pub fn plant_reactor_charge(
+    mut commands: Commands,
+    mut charge_events: EventReader<PlantChargeEvent>,
+    mut sabotage: ResMut<ReactorSabotage>,
+    mut protagonist_query: Query<(Entity, &Transform, &mut Inventory), With<Protagonist>>,
+    spatial_query: SpatialQuery,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut message_display: ResMut<MessageDisplay>,
+) {
+    for event in charge_events.read() {
+        let Ok((protagonist_entity, transform, mut inventory)) = protagonist_query.get_single_mut() else {
+            continue;
+        };
+
+        if !matches!(sabotage.state, SabotageState::Intact) {
+            display_message("The reactor is already rigged", Color::WHITE, &mut message_display);
+            continue;
+        }
+
+        let hit = spatial_query.cast_ray(
+            transform.translation,
+            transform.forward(),
+            CHARGE_REACH,
+            true,
+            SpatialQueryFilter::from_excluded_entities([protagonist_entity]),
+        );
+        let Some(hit) = hit.filter(|hit| {
+            is_reactor_surface(transform.translation + transform.forward() * hit.time_of_impact)
+        }) else {
+            display_message("Face the reactor wall to plant a charge", Color::WHITE, &mut message_display);
+            continue;
+        };
+
+        if !inventory.remove(&event.item, 1) {
+            continue;
+        }
+
+        let position = transform.translation + transform.forward() * hit.time_of_impact;
+        commands.spawn((
+            PbrBundle {
+                mesh: meshes.add(Cuboid::new(CHARGE_SIZE.x, CHARGE_SIZE.y, CHARGE_SIZE.z)),
+                material: materials.add(StandardMaterial {
+                    base_color: Color::srgb(0.3, 0.3, 0.25),
+                    perceptual_roughness: 0.9,
+                    ..default()
+                }),
+                // Sit flush against the wall, facing out along the surface normal
+                transform: Transform::from_translation(position + hit.normal * CHARGE_SIZE.z / 2.0)
+                    .looking_to(-hit.normal, Vec3::Y),
+                ..default()
+            },
+            ReactorCharge,
+            Name::new("ReactorCharge"),
+        )).with_children(|parent| {
+            parent.spawn(PointLightBundle {
+                point_light: PointLight {
+                    color: ALARM_COLOR,
+                    intensity: 0.0,
+                    range: 30.0,
+                    ..default()
+                },
+                transform: Transform::from_xyz(0.0, 0.0, -CHARGE_SIZE.z),
+                ..default()
+            });
+        });
+
+        sabotage.state = SabotageState::Armed(Timer::from_seconds(CHARGE_ARM_TIME, TimerMode::Once));
+        display_message("CHARGE ARMED - GET CLEAR", ALARM_COLOR, &mut message_display);
+    }
+}
+
+pub fn update_reactor_sabotage(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut sabotage: ResMut<ReactorSabotage>,
+    mut surge: ResMut<SentrySurge>,
+    charge_query: Query<(Entity, &Transform, &Children), With<ReactorCharge>>,
+    mut charge_light_query: Query<&mut PointLight>,
+    mut protagonist_query: Query<(&mut Transform, &mut LinearVelocity, &mut Health), (With<Protagonist>, Without<ReactorCharge>)>,
+    mut lighting: ResMut<LightingStack>,
+    mut countdown_query: Query<&mut Text, With<SabotageCountdownText>>,
+    explosion_materials: Res<ExplosionMaterials>,
+    mut explosion_counter: ResMut<ExplosionCounter>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut noise_events: EventWriter<NoiseEvent>,
+    mut message_display: ResMut<MessageDisplay>,
+) {
+    let mut countdown = String::new();
+
+    match &mut sabotage.state {
+        SabotageState::Intact | SabotageState::Complete => {}
+        SabotageState::Armed(timer) => {
+            timer.tick(time.delta());
+            countdown = format!("DETONATION {:.1}", timer.remaining_secs());
+
+            // Blink faster as the timer runs down
+            let blink_rate = 2.0 + 10.0 * timer.fraction();
+            let blink_on = (time.elapsed_seconds() * blink_rate).sin() > 0.0;
+            for (_, _, children) in charge_query.iter() {
+                for &child in children.iter() {
+                    if let Ok(mut light) = charge_light_query.get_mut(child) {
+                        light.intensity = if blink_on { CHARGE_BLINK_INTENSITY } else { 0.0 };
+                    }
+                }
+            }
+
+            if timer.finished() {
+                for (entity, charge_transform, _) in charge_query.iter() {
+                    spawn_explosion_effects(
+                        &mut commands,
+                        &explosion_materials,
+                        charge_transform.translation,
+                        &mut explosion_counter,
+                        0.0,
+                        true,
+                        &mut materials,
+                        &time,
+                    );
+                    noise_events.send(NoiseEvent {
+                        position: charge_transform.translation,
+                        radius: CHARGE_NOISE_RADIUS,
+                    });
+                    commands.entity(entity).despawn_recursive();
+                }
+
+                // Secondary blasts around the wall
+                for i in 0..DETONATION_EXPLOSIONS {
+                    let angle = i as f32 * std::f32::consts::TAU / DETONATION_EXPLOSIONS as f32;
+                    let position = REACTOR_POSITION + Vec3::new(
+                        CYLINDER_RADIUS * angle.cos(),
+                        CYLINDER_HEIGHT / 2.0,
+                        CYLINDER_RADIUS * angle.sin(),
+                    );
+                    spawn_explosion_effects(
+                        &mut commands,
+                        &explosion_materials,
+                        position,
+                        &mut explosion_counter,
+                        0.0,
+                        true,
+                        &mut materials,
+                        &time,
+                    );
+                }
+
+                surge.active = true;
+                display_message("REACTOR CRITICAL - GET CLEAR OF THE BLAST", ALARM_COLOR, &mut message_display);
+                sabotage.state = SabotageState::Escape(Timer::from_seconds(ESCAPE_TIME, TimerMode::Once));
+            }
+        }
+        SabotageState::Escape(timer) => {
+            timer.tick(time.delta());
+            countdown = format!("MELTDOWN {:.0}", timer.remaining_secs());
+
+            // Pulse the alarm lighting
+            lighting.push(LightingZone::Alarm, 1.0);
+
+            if let Ok((mut transform, mut velocity, mut health)) = protagonist_query.get_single_mut() {
+                let distance = transform.translation.with_y(0.0).distance(REACTOR_POSITION.with_y(0.0));
+                let escaped = distance > ESCAPE_SAFE_DISTANCE;
+                if escaped || timer.finished() {
+                    surge.active = false;
+                    lighting.pop(LightingZone::Alarm);
+                }
+
+                if escaped {
+                    display_message("OBJECTIVE COMPLETE - THE REACTOR IS DOWN", Color::WHITE, &mut message_display);
+                    sabotage.state = SabotageState::Complete;
+                } else if timer.finished() {
+                    // Caught in the meltdown, try again from the start
+                    display_message("CAUGHT IN THE MELTDOWN", ALARM_COLOR, &mut message_display);
+                    transform.translation = PROTAGONIST_START.position;
+                    velocity.0 = Vec3::ZERO;
+                    health.current = health.max;
+                    sabotage.state = SabotageState::Intact;
+                }
+            }
+        }
+    }
+
+    if let Ok(mut text) = countdown_query.get_single_mut() {
+        if text.sections[0].value != countdown {
+            text.sections[0].value = countdown;
+        }
+    }
+}
@@ -0,0 +1,193 @@
+use bevy::prelude::*;
+use avian3d::prelude::*;
+use crate::components::Protagonist;
+use crate::systems::core::sentry::NoiseEvent;
+use crate::systems::environments::terrain::{Terrain, TerrainHeightfield};
+use crate::systems::environments::tram::TramRider;
+
+// Footsteps
+const FOOTSTEP_WALK_INTERVAL: f32 = 0.5;  // Seconds between steps
+const FOOTSTEP_RUN_INTERVAL: f32 = 0.3;
+const FOOTSTEP_MIN_SPEED: f32 = 2.0;  // Slower than this is standing still
+const FOOTSTEP_RUN_SPEED: f32 = 40.0;  // Between walking and running speed
+const FOOTSTEP_NOISE_RADIUS: f32 = 20.0;  // A walking step on rock
+const FOOTSTEP_RUN_MULTIPLIER: f32 = 2.5;
+const FOOTSTEP_CRAWL_MULTIPLIER: f32 = 0.3;
+
+// How far below the protagonist to look for the ground
+const GROUND_PROBE_DISTANCE: f32 = 3.0;
+
+// What a collider is made of. Terrain doesn't carry one; its regions come from
+// `TerrainHeightfield::material_at` instead.
+#[derive(Component, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum SurfaceMaterial {
+    #[default]
+    Rock,
+    Snow,
+    Ice,
+    Metal,  // Gratings, hulls and pipes
+}
+
+impl SurfaceMaterial {
+    pub fn friction(self) -> f32 {
+        match self {
+            SurfaceMaterial::Rock => 0.2,  // The terrain friction the protagonist was tuned on
+            SurfaceMaterial::Snow => 0.15,
+            SurfaceMaterial::Ice => 0.02,
+            SurfaceMaterial::Metal => 0.6,
+        }
+    }
+
+    // Multiplier on how far footsteps carry
+    pub fn footstep_loudness(self) -> f32 {
+        match self {
+            SurfaceMaterial::Rock => 1.0,
+            SurfaceMaterial::Snow => 0.6,
+            SurfaceMaterial::Ice => 0.3,
+            SurfaceMaterial::Metal => 2.5,
+        }
+    }
+
+    // How clearly tracks show, 0 for none
+    pub fn footprint_visibility(self) -> f32 {
+        match self {
+            SurfaceMaterial::Rock => 0.2,
+            SurfaceMaterial::Snow => 1.0,
+            SurfaceMaterial::Ice => 0.0,
+            SurfaceMaterial::Metal => 0.0,
+        }
+    }
+
+    // Grip multiplier for tank tracks
+    pub fn traction(self) -> f32 {
+        match self {
+            SurfaceMaterial::Rock => 1.0,
+            SurfaceMaterial::Snow => 0.6,
+            SurfaceMaterial::Ice => 0.2,
+            SurfaceMaterial::Metal => 1.2,
+        }
+    }
+
+    // Vertex tint for terrain regions
+    pub fn tint(self) -> [f32; 4] {
+        match self {
+            SurfaceMaterial::Rock => [1.0, 1.0, 1.0, 1.0],
+            SurfaceMaterial::Snow => [1.8, 1.8, 1.9, 1.0],
+            SurfaceMaterial::Ice => [0.8, 1.1, 1.6, 1.0],
+            SurfaceMaterial::Metal => [0.7, 0.7, 0.75, 1.0],
+        }
+    }
+}
+
+// The surface the protagonist is standing on, from the last time they touched ground
+#[derive(Component, Default)]
+pub struct GroundSurface {
+    pub material: SurfaceMaterial,
+    pub contact: Option<Vec3>,  // Point under the protagonist, None while off the ground
+}
+
+// The material at a ray hit, from the components of the collider that was hit
+pub fn surface_at_hit(
+    surface: (Option<&SurfaceMaterial>, Option<&Terrain>),
+    point: Vec3,
+    heightfield: &TerrainHeightfield,
+) -> SurfaceMaterial {
+    match surface {
+        (Some(material), _) => *material,
+        (None, Some(terrain)) if terrain.is_icy => SurfaceMaterial::Ice,
+        (None, Some(_)) => heightfield.material_at(point.x, point.z),
+        _ => SurfaceMaterial::default(),
+    }
+}
+
+// Colliders take their friction from their material
+pub fn apply_surface_friction(
+    mut commands: Commands,
+    surface_query: Query<(Entity, &SurfaceMaterial), Changed<SurfaceMaterial>>,
+) {
+    for (entity, material) in surface_query.iter() {
+        commands.entity(entity).insert(Friction {
+            dynamic_coefficient: material.friction(),
+            static_coefficient: material.friction(),
+            combine_rule: CoefficientCombine::Min,
+        });
+    }
+}
+
+// Track what the protagonist is standing on. Terrain friction is the same everywhere,
+// so the protagonist carries the friction of the region underfoot instead.
+pub fn update_ground_surface(
+    mut protagonist_query: Query<(Entity, &Transform, &mut GroundSurface, &mut Friction), With<Protagonist>>,
+    surface_query: Query<(Option<&SurfaceMaterial>, Option<&Terrain>)>,
+    heightfield: Res<TerrainHeightfield>,
+    spatial_query: SpatialQuery,
+) {
+    let Ok((entity, transform, mut ground, mut friction)) = protagonist_query.get_single_mut() else {
+        return;
+    };
+
+    let Some(hit) = spatial_query.cast_ray(
+        transform.translation,
+        Dir3::NEG_Y,
+        GROUND_PROBE_DISTANCE,
+        true,
+        SpatialQueryFilter::from_excluded_entities([entity]),
+    ) else {
+        ground.contact = None;
+        return;
+    };
+
+    let point = transform.translation + Vec3::NEG_Y * hit.time_of_impact;
+    let surface = surface_query.get(hit.entity).unwrap_or((None, None));
+    let material = surface_at_hit(surface, point, &heightfield);
+    ground.contact = Some(point);
+
+    if ground.material != material {
+        ground.material = material;
+        friction.dynamic_coefficient = material.friction();
+        friction.static_coefficient = material.friction();
+    }
+}
+
+// Walking makes noise sentries can hear, louder when running and on metal
+pub fn footstep_noise(
+    protagonist_query: Query<(&Transform, &Protagonist, &LinearVelocity, &GroundSurface), Without<TramRider>>,
+    mut noise_events: EventWriter<NoiseEvent>,
+    mut since_last_step: Local<f32>,
+    time: Res<Time>,
+) {
+    let Ok((transform, protagonist, velocity, ground)) = protagonist_query.get_single() else {
+        return;
+    };
+
+    let on_foot = !protagonist.is_falling
+        && !protagonist.is_swimming
+        && !protagonist.is_climbing
+        && !protagonist.is_driving
+        && !protagonist.is_dirigible;
+    let speed = Vec3::new(velocity.x, 0.0, velocity.z).length();
+    if !on_foot || speed < FOOTSTEP_MIN_SPEED {
+        *since_last_step = 0.0;
+        return;
+    }
+
+    let running = speed >= FOOTSTEP_RUN_SPEED;
+    let interval = if running { FOOTSTEP_RUN_INTERVAL } else { FOOTSTEP_WALK_INTERVAL };
+    *since_last_step += time.delta_seconds();
+    if *since_last_step < interval {
+        return;
+    }
+    *since_last_step = 0.0;
+
+    let pace = if protagonist.is_crawling {
+        FOOTSTEP_CRAWL_MULTIPLIER
+    } else if running {
+        FOOTSTEP_RUN_MULTIPLIER
+    } else {
+        1.0
+    };
+    noise_events.send(NoiseEvent {
+        position: transform.translation,
+        radius: FOOTSTEP_NOISE_RADIUS * pace * ground.material.footstep_loudness(),
+    });
+}
@@ -1,14 +1,18 @@
 use bevy::prelude::*;
 use bevy::render::mesh::Indices;
+use bevy::tasks::{block_on, futures_lite::future, AsyncComputeTaskPool, Task};
 use avian3d::prelude::*;
-use avian3d::collision::{ColliderMarker, CollisionLayers, LayerMask, PhysicsLayer};
+use avian3d::collision::{CollisionLayers, LayerMask, PhysicsLayer};
 use noise::OpenSimplex;
 use noise::NoiseFn;
-use crate::components::Protagonist;
+use std::collections::{HashMap, HashSet};
+use crate::components::{Protagonist, Sentry, Tank};
+use crate::systems::environments::surface::SurfaceMaterial;
+use crate::systems::environments::airlock::{AIRLOCK_POSITION, AIRLOCK_LENGTH, AIRLOCK_RADIUS};
+use crate::systems::environments::geothermal::{PUMP_HOUSE_POSITION, PUMP_HOUSE_SIZE};
 
 // Terrain Generation Parameters
 const TERRAIN_RADIUS: f32 = 5000.0;
-const TERRAIN_RESOLUTION: i32 = 1000;
 const TERRAIN_SEED: u32 = 42;
 const BASE_HEIGHT: f32 = -30.0;
 const Y_OFFSET: f32 = 30.0;
@@ -20,6 +24,39 @@ const AMPLITUDE_BASE: f64 = 0.5;
 const NOISE_SCALE: f64 = 0.002;
 const HEIGHT_MULTIPLIER: f64 = 100.0;
 
+// Chunking
+const CHUNK_SIZE: f32 = 500.0;
+const CHUNKS_PER_SIDE: i32 = (2.0 * TERRAIN_RADIUS / CHUNK_SIZE) as i32;
+const CHUNK_LOD_RESOLUTIONS: [u32; 4] = [64, 32, 16, 8];  // Quads per chunk side, finest first
+const CHUNK_LOD_DISTANCES: [f32; 3] = [750.0, 1500.0, 3000.0];  // Switch to the next LOD past these
+const CHUNK_COLLIDER_RANGE: f32 = 150.0;  // Stream in colliders this close to a body
+const CHUNK_NORMAL_SAMPLE: f32 = CHUNK_SIZE / 64.0;  // Same for every LOD so seams shade alike
+
+// Surface types, by the up component of the ground normal
+const SURFACE_FLAT_NORMAL_Y: f32 = 0.95;
+const SURFACE_CLIFF_NORMAL_Y: f32 = 0.7;
+
+// Surface materials: hand-placed regions first, then noise and altitude
+const SURFACE_REGIONS: &[(Vec2, f32, SurfaceMaterial)] = &[  // Center (x, z), radius, material
+    (Vec2::new(300.0, 400.0), 150.0, SurfaceMaterial::Ice),  // Frozen lake south of the start
+    (Vec2::new(1800.0, -1250.0), 250.0, SurfaceMaterial::Snow),  // Drifts behind the garage
+];
+const SURFACE_NOISE_SEED: u32 = TERRAIN_SEED + 1;
+const SURFACE_NOISE_SCALE: f64 = 0.0008;
+const SURFACE_ICE_THRESHOLD: f64 = 0.45;  // Noise above this is ice
+const SURFACE_SNOW_THRESHOLD: f64 = -0.35;  // Noise below this is snow
+const SNOW_LINE: f32 = 45.0;  // Everything above this height is snow
+
+// Ground leveled by hand for structures that need to sit flush on it
+const TERRAIN_PADS: &[(Vec2, Vec2, f32)] = &[  // Center (x, z), half extents, world height
+    (Vec2::new(AIRLOCK_POSITION.x, AIRLOCK_POSITION.z), Vec2::new(AIRLOCK_LENGTH / 2.0 + 40.0, AIRLOCK_RADIUS * 2.0), 0.0),  // Airlock bed, with an apron at each door
+    (Vec2::new(PUMP_HOUSE_POSITION.x, PUMP_HOUSE_POSITION.z), Vec2::new(PUMP_HOUSE_SIZE.x, PUMP_HOUSE_SIZE.z), 0.0),  // Geothermal pump house
+];
+const TERRAIN_PAD_BLEND: f32 = 60.0;  // Distance over which a pad eases back into the noise
+
+// Neighbouring chunk offsets, in the order edges are stitched: -X, +X, -Z, +Z
+const CHUNK_SIDES: [IVec2; 4] = [IVec2::NEG_X, IVec2::X, IVec2::NEG_Y, IVec2::Y];
+
 #[derive(PhysicsLayer, Clone, Copy, Debug, Default)]
 pub enum GameLayer {
     #[default]
@@ -32,128 +69,433 @@ pub struct Terrain {
     pub is_icy: bool,
 }
 
-pub fn spawn_terrain(
-    mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
-    asset_server: Res<AssetServer>,
-) {
-    let noise = OpenSimplex::new(TERRAIN_SEED);
-    
-    let mut vertices = Vec::new();
-    let mut indices = Vec::new();
-    let mut uvs = Vec::new();
-    
-    let mut vertex_map = vec![vec![-1i32; TERRAIN_RESOLUTION as usize + 1]; TERRAIN_RESOLUTION as usize + 1];
-    let mut vertex_count = 0;
-    
-    // Generate vertices in a grid
-    for z in 0..=TERRAIN_RESOLUTION {
-        for x in 0..=TERRAIN_RESOLUTION {
-            let px = (x as f32 / TERRAIN_RESOLUTION as f32) * 2.0 - 1.0;
-            let pz = (z as f32 / TERRAIN_RESOLUTION as f32) * 2.0 - 1.0;
-            
-            let dist = (px * px + pz * pz).sqrt();
-            if dist <= 1.0 {
-                let wx = px * TERRAIN_RADIUS;
-                let wz = pz * TERRAIN_RADIUS;
-                
-                let height = (0..OCTAVE_COUNT).map(|octave| {
-                    let frequency = FREQUENCY_BASE.powi(octave);
-                    let amplitude = AMPLITUDE_BASE.powi(octave);
-                    noise.get([
-                        wx as f64 * NOISE_SCALE * frequency,
-                        wz as f64 * NOISE_SCALE * frequency
-                    ]) * amplitude * HEIGHT_MULTIPLIER
-                }).sum::<f64>() as f32;
-
-                let final_height = height + BASE_HEIGHT;
-                vertices.push([wx, final_height, wz]);
-                uvs.push([
-                    (px + 1.0) * 0.5,
-                    (pz + 1.0) * 0.5,
-                ]);
-                
-                vertex_map[z as usize][x as usize] = vertex_count;
-                vertex_count += 1;
-            }
+#[derive(Component)]
+pub struct TerrainChunk {
+    coord: IVec2,
+    built: Option<(usize, [usize; 4])>,  // LOD and neighbour LODs of the current mesh
+}
+
+#[derive(Component)]
+pub struct ChunkMeshTask {
+    task: Task<Mesh>,
+    lod: usize,
+    seams: [usize; 4],
+}
+
+#[derive(Component)]
+pub struct ChunkColliderTask(Task<Collider>);
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TerrainSurface {
+    Flat,
+    Slope,
+    Cliff,
+    OutOfBounds,  // Past the edge of the terrain disc
+}
+
+impl TerrainSurface {
+    pub fn is_walkable(self) -> bool {
+        matches!(self, TerrainSurface::Flat | TerrainSurface::Slope)
+    }
+}
+
+// The terrain's shape, answered straight from the noise so gameplay code can ask about
+// the ground anywhere without waiting for a chunk collider or casting a ray. Heights
+// follow the finest chunk grid, so they match the colliders and LOD0 meshes exactly.
+// Chunks drawn at a coarser LOD (past the first `CHUNK_LOD_DISTANCES`) are only an
+// approximation of this surface. That is deliberate: something placed far away, like a
+// sentry from `periodic_sentry_spawn`, may float or sink a little on the distant mesh,
+// but its chunk refines to LOD0 well before the protagonist is close enough to notice,
+// and from then on it sits exactly on the drawn ground.
+#[derive(Resource, Clone)]
+pub struct TerrainHeightfield {
+    noise: OpenSimplex,
+    surface_noise: OpenSimplex,
+}
+
+impl Default for TerrainHeightfield {
+    fn default() -> Self {
+        Self {
+            noise: OpenSimplex::new(TERRAIN_SEED),
+            surface_noise: OpenSimplex::new(SURFACE_NOISE_SEED),
+        }
+    }
+}
+
+impl TerrainHeightfield {
+    // Height of the noise surface in the terrain's local space
+    fn sample(&self, x: f32, z: f32) -> f32 {
+        let height = (0..OCTAVE_COUNT).map(|octave| {
+            let frequency = FREQUENCY_BASE.powi(octave);
+            let amplitude = AMPLITUDE_BASE.powi(octave);
+            self.noise.get([
+                x as f64 * NOISE_SCALE * frequency,
+                z as f64 * NOISE_SCALE * frequency
+            ]) * amplitude * HEIGHT_MULTIPLIER
+        }).sum::<f64>() as f32 + BASE_HEIGHT;
+
+        TERRAIN_PADS.iter().fold(height, |height, &(center, half_extents, pad_height)| {
+            let outside = ((Vec2::new(x, z) - center).abs() - half_extents).max(Vec2::ZERO).length();
+            let blend = (outside / TERRAIN_PAD_BLEND).min(1.0);
+            let blend = blend * blend * (3.0 - 2.0 * blend);
+            let pad_height = pad_height - Y_OFFSET;
+            pad_height + (height - pad_height) * blend
+        })
+    }
+
+    // Local height and slope (dy/dx, dy/dz) of the grid triangle under (x, z), split
+    // the same way as `build_chunk_grid`
+    fn triangle_at(&self, x: f32, z: f32) -> (f32, Vec2) {
+        let cell = CHUNK_SIZE / CHUNK_LOD_RESOLUTIONS[0] as f32;
+        let grid = (Vec2::new(x, z) + Vec2::splat(TERRAIN_RADIUS)) / cell;
+        let corner = grid.floor();
+        let (fx, fz) = (grid.x - corner.x, grid.y - corner.y);
+        let (x0, z0) = (corner.x * cell - TERRAIN_RADIUS, corner.y * cell - TERRAIN_RADIUS);
+
+        let h00 = self.sample(x0, z0);
+        let h10 = self.sample(x0 + cell, z0);
+        let h01 = self.sample(x0, z0 + cell);
+        let h11 = self.sample(x0 + cell, z0 + cell);
+
+        if fx + fz <= 1.0 {
+            let slope = Vec2::new(h10 - h00, h01 - h00);
+            (h00 + fx * slope.x + fz * slope.y, slope / cell)
+        } else {
+            let slope = Vec2::new(h11 - h01, h11 - h10);
+            (h11 - (1.0 - fx) * slope.x - (1.0 - fz) * slope.y, slope / cell)
+        }
+    }
+
+    pub fn contains(&self, x: f32, z: f32) -> bool {
+        Vec2::new(x, z).length() <= TERRAIN_RADIUS
+    }
+
+    // World-space height of the ground
+    pub fn height_at(&self, x: f32, z: f32) -> f32 {
+        self.triangle_at(x, z).0 + Y_OFFSET
+    }
+
+    pub fn normal_at(&self, x: f32, z: f32) -> Vec3 {
+        let (_, slope) = self.triangle_at(x, z);
+        Vec3::new(-slope.x, 1.0, -slope.y).normalize()
+    }
+
+    pub fn material_at(&self, x: f32, z: f32) -> SurfaceMaterial {
+        let position = Vec2::new(x, z);
+        if let Some((_, _, material)) = SURFACE_REGIONS.iter()
+            .find(|(center, radius, _)| center.distance(position) <= *radius)
+        {
+            return *material;
+        }
+
+        if self.height_at(x, z) > SNOW_LINE {
+            return SurfaceMaterial::Snow;
+        }
+
+        let region = self.surface_noise.get([x as f64 * SURFACE_NOISE_SCALE, z as f64 * SURFACE_NOISE_SCALE]);
+        if region > SURFACE_ICE_THRESHOLD {
+            SurfaceMaterial::Ice
+        } else if region < SURFACE_SNOW_THRESHOLD {
+            SurfaceMaterial::Snow
+        } else {
+            SurfaceMaterial::Rock
         }
     }
-    
-    // Generate triangles
-    for z in 0..TERRAIN_RESOLUTION {
-        for x in 0..TERRAIN_RESOLUTION {
-            let tl = vertex_map[z as usize][x as usize];
-            let tr = vertex_map[z as usize][(x + 1) as usize];
-            let bl = vertex_map[(z + 1) as usize][x as usize];
-            let br = vertex_map[(z + 1) as usize][(x + 1) as usize];
-            
-            if tl >= 0 && tr >= 0 && bl >= 0 && br >= 0 {
-                indices.extend_from_slice(&[
-                    tl as u32, tr as u32, bl as u32,
-                    tr as u32, br as u32, bl as u32,
-                ]);
+
+    pub fn surface_at(&self, x: f32, z: f32) -> TerrainSurface {
+        if !self.contains(x, z) {
+            return TerrainSurface::OutOfBounds;
+        }
+
+        let steepness = self.normal_at(x, z).y;
+        if steepness >= SURFACE_FLAT_NORMAL_Y {
+            TerrainSurface::Flat
+        } else if steepness >= SURFACE_CLIFF_NORMAL_Y {
+            TerrainSurface::Slope
+        } else {
+            TerrainSurface::Cliff
+        }
+    }
+}
+
+fn chunk_origin(coord: IVec2) -> Vec2 {
+    Vec2::splat(-TERRAIN_RADIUS) + coord.as_vec2() * CHUNK_SIZE
+}
+
+fn chunk_coord(position: Vec2) -> IVec2 {
+    ((position + Vec2::splat(TERRAIN_RADIUS)) / CHUNK_SIZE).floor().as_ivec2()
+}
+
+// Horizontal distance from `position` to the nearest point of the chunk
+fn chunk_distance(coord: IVec2, position: Vec2) -> f32 {
+    let origin = chunk_origin(coord);
+    position.distance(position.clamp(origin, origin + Vec2::splat(CHUNK_SIZE)))
+}
+
+fn chunk_lod(coord: IVec2, viewer: Vec2) -> usize {
+    let distance = chunk_distance(coord, viewer);
+    CHUNK_LOD_DISTANCES.iter().filter(|&&lod_distance| distance > lod_distance).count()
+}
+
+struct ChunkGrid {
+    positions: Vec<Vec3>,
+    triangles: Vec<[u32; 3]>,
+}
+
+// Sample a chunk at `resolution`, snapping each edge onto its neighbour's coarser grid
+fn build_chunk_grid(heightfield: &TerrainHeightfield, coord: IVec2, resolution: u32, seam_resolutions: [u32; 4]) -> ChunkGrid {
+    let origin = chunk_origin(coord);
+    let cell = CHUNK_SIZE / resolution as f32;
+    let row = resolution + 1;
+    let index = |x: u32, z: u32| (z * row + x) as usize;
+
+    let mut positions: Vec<Vec3> = (0..=resolution)
+        .flat_map(|z| (0..=resolution).map(move |x| (x, z)))
+        .map(|(x, z)| {
+            let wx = origin.x + x as f32 * cell;
+            let wz = origin.y + z as f32 * cell;
+            Vec3::new(wx, heightfield.sample(wx, wz), wz)
+        })
+        .collect();
+
+    // Edge vertices the coarser neighbour doesn't have are moved onto its edge line
+    for (side, &neighbour_resolution) in seam_resolutions.iter().enumerate() {
+        let step = resolution / neighbour_resolution.min(resolution);
+        if step <= 1 {
+            continue;
+        }
+
+        let edge = |along: u32| match side {
+            0 => index(0, along),
+            1 => index(resolution, along),
+            2 => index(along, 0),
+            _ => index(along, resolution),
+        };
+        for along in 0..=resolution {
+            let offset = along % step;
+            if offset == 0 {
+                continue;
             }
+            let low = positions[edge(along - offset)].y;
+            let high = positions[edge(along - offset + step)].y;
+            positions[edge(along)].y = low + (high - low) * offset as f32 / step as f32;
         }
     }
-    
-    // Calculate normals
-    let mut normals = vec![[0.0, 1.0, 0.0]; vertices.len()];
-    for chunk in indices.chunks(3) {
-        if let [i1, i2, i3] = chunk {
-            let v1 = Vec3::from(vertices[*i1 as usize]);
-            let v2 = Vec3::from(vertices[*i2 as usize]);
-            let v3 = Vec3::from(vertices[*i3 as usize]);
-            
-            let normal = (v2 - v1).cross(v3 - v1).normalize();
-            normals[*i1 as usize] = normal.into();
-            normals[*i2 as usize] = normal.into();
-            normals[*i3 as usize] = normal.into();
+
+    // Only keep quads inside the terrain disc
+    let inside: Vec<bool> = positions.iter()
+        .map(|position| Vec2::new(position.x, position.z).length() <= TERRAIN_RADIUS)
+        .collect();
+
+    let mut triangles = Vec::new();
+    for z in 0..resolution {
+        for x in 0..resolution {
+            let (tl, tr, bl, br) = (index(x, z), index(x + 1, z), index(x, z + 1), index(x + 1, z + 1));
+            if inside[tl] && inside[tr] && inside[bl] && inside[br] {
+                triangles.push([tl as u32, bl as u32, tr as u32]);
+                triangles.push([tr as u32, bl as u32, br as u32]);
+            }
         }
     }
 
+    ChunkGrid { positions, triangles }
+}
+
+fn build_chunk_mesh(heightfield: &TerrainHeightfield, coord: IVec2, lod: usize, seams: [usize; 4]) -> Mesh {
+    let grid = build_chunk_grid(
+        heightfield,
+        coord,
+        CHUNK_LOD_RESOLUTIONS[lod],
+        seams.map(|seam| CHUNK_LOD_RESOLUTIONS[seam]),
+    );
+
+    let normals: Vec<[f32; 3]> = grid.positions.iter().map(|position| {
+        let dx = heightfield.sample(position.x + CHUNK_NORMAL_SAMPLE, position.z)
+            - heightfield.sample(position.x - CHUNK_NORMAL_SAMPLE, position.z);
+        let dz = heightfield.sample(position.x, position.z + CHUNK_NORMAL_SAMPLE)
+            - heightfield.sample(position.x, position.z - CHUNK_NORMAL_SAMPLE);
+        Vec3::new(-dx, 2.0 * CHUNK_NORMAL_SAMPLE, -dz).normalize().into()
+    }).collect();
+
+    // UVs span the whole terrain so the texture lines up across chunks
+    let uvs: Vec<[f32; 2]> = grid.positions.iter()
+        .map(|position| [
+            (position.x / TERRAIN_RADIUS + 1.0) * 0.5,
+            (position.z / TERRAIN_RADIUS + 1.0) * 0.5,
+        ])
+        .collect();
+
+    let colors: Vec<[f32; 4]> = grid.positions.iter()
+        .map(|position| heightfield.material_at(position.x, position.z).tint())
+        .collect();
+
+    let positions: Vec<[f32; 3]> = grid.positions.iter().map(|&position| position.into()).collect();
+    let indices: Vec<u32> = grid.triangles.iter().flatten().copied().collect();
 
     let mut mesh = Mesh::new(
         bevy::render::render_resource::PrimitiveTopology::TriangleList,
         bevy::render::render_asset::RenderAssetUsages::RENDER_WORLD,
     );
-    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, vertices);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
     mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
     mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, colors);
     mesh.insert_indices(Indices::U32(indices));
+    mesh
+}
 
-    commands.spawn(PbrBundle {
-            mesh: meshes.add(mesh),
-            material: materials.add(StandardMaterial {
-                base_color_texture: Some(asset_server.load("textures/8k_mars.png")),
-                perceptual_roughness: 1.0,
-                metallic: 0.0,
-                reflectance: 0.0,
-                alpha_mode: AlphaMode::Opaque,
-                double_sided: true,
-                cull_mode: None,
-                ..default()
-            }),
-            transform: Transform::from_xyz(0.0, Y_OFFSET, 0.0),
-            ..default()
-        })
-        .insert(ColliderConstructor::TrimeshFromMesh)
-        .insert(RigidBody::Static)
-        .insert(ColliderMarker)
-        .insert(Friction {
-            dynamic_coefficient: 0.2,
-            static_coefficient: 0.2,
-            combine_rule: CoefficientCombine::Min,
-        })
-        .insert(Restitution {
-            coefficient: 0.0,
-            combine_rule: CoefficientCombine::Min,
-        })
-        .insert(CollisionLayers {
-            memberships: GameLayer::Terrain.into(),  // Terrain layer
-            filters: LayerMask(0b111),  // Can collide with Default, Player, and Terrain
-        })
-        .insert(Terrain { is_icy: false });
+// Colliders always use the finest LOD so bodies rest where the closest mesh is drawn
+fn build_chunk_collider(heightfield: &TerrainHeightfield, coord: IVec2) -> Collider {
+    let resolution = CHUNK_LOD_RESOLUTIONS[0];
+    let grid = build_chunk_grid(heightfield, coord, resolution, [resolution; 4]);
+    Collider::trimesh(grid.positions, grid.triangles)
+}
+
+pub fn spawn_terrain(
+    mut commands: Commands,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    asset_server: Res<AssetServer>,
+) {
+    let material = materials.add(StandardMaterial {
+        base_color_texture: Some(asset_server.load("textures/8k_mars.png")),
+        perceptual_roughness: 1.0,
+        metallic: 0.0,
+        reflectance: 0.0,
+        alpha_mode: AlphaMode::Opaque,
+        double_sided: true,
+        cull_mode: None,
+        ..default()
+    });
+
+    // Meshes and colliders are filled in by `stream_terrain_chunks`
+    for z in 0..CHUNKS_PER_SIDE {
+        for x in 0..CHUNKS_PER_SIDE {
+            let coord = IVec2::new(x, z);
+            if chunk_distance(coord, Vec2::ZERO) >= TERRAIN_RADIUS {
+                continue;
+            }
+
+            commands.spawn(PbrBundle {
+                    material: material.clone(),
+                    transform: Transform::from_xyz(0.0, Y_OFFSET, 0.0),
+                    ..default()
+                })
+                .insert(RigidBody::Static)
+                // High so whatever stands on it decides; see `update_ground_surface`
+                .insert(Friction {
+                    dynamic_coefficient: 1.0,
+                    static_coefficient: 1.0,
+                    combine_rule: CoefficientCombine::Min,
+                })
+                .insert(Restitution {
+                    coefficient: 0.0,
+                    combine_rule: CoefficientCombine::Min,
+                })
+                .insert(CollisionLayers {
+                    memberships: GameLayer::Terrain.into(),  // Terrain layer
+                    filters: LayerMask(0b111),  // Can collide with Default, Player, and Terrain
+                })
+                .insert(Terrain { is_icy: false })
+                .insert(TerrainChunk { coord, built: None })
+                .insert(Name::new(format!("TerrainChunk {} {}", x, z)));
+        }
+    }
+}
+
+// Pick each chunk's LOD from its distance to the protagonist and keep colliders only
+// around the protagonist, tanks and sentries. Meshes and colliders are built on the
+// async compute pool, except a collider under a body that is already standing on it.
+pub fn stream_terrain_chunks(
+    mut commands: Commands,
+    heightfield: Res<TerrainHeightfield>,
+    chunk_query: Query<(Entity, &TerrainChunk, Has<ChunkMeshTask>, Has<ChunkColliderTask>, Has<Collider>)>,
+    protagonist_query: Query<&Transform, With<Protagonist>>,
+    body_query: Query<&Transform, Or<(With<Protagonist>, With<Tank>, With<Sentry>)>>,
+) {
+    let Ok(protagonist_transform) = protagonist_query.get_single() else {
+        return;
+    };
+    let viewer = protagonist_transform.translation.xz();
+
+    // LODs first, so each chunk can be stitched against its neighbours
+    let lods: HashMap<IVec2, usize> = chunk_query.iter()
+        .map(|(_, chunk, _, _, _)| (chunk.coord, chunk_lod(chunk.coord, viewer)))
+        .collect();
+
+    let mut occupied = HashSet::new();
+    let mut in_range = HashSet::new();
+    let mut keep = HashSet::new();  // Colliders are dropped only well out of range
+    for transform in body_query.iter() {
+        let position = transform.translation.xz();
+        let center = chunk_coord(position);
+        occupied.insert(center);
+
+        for dz in -1..=1 {
+            for dx in -1..=1 {
+                let coord = center + IVec2::new(dx, dz);
+                let distance = chunk_distance(coord, position);
+                if distance < CHUNK_COLLIDER_RANGE {
+                    in_range.insert(coord);
+                }
+                if distance < CHUNK_COLLIDER_RANGE * 2.0 {
+                    keep.insert(coord);
+                }
+            }
+        }
+    }
+
+    let pool = AsyncComputeTaskPool::get();
+    for (entity, chunk, building_mesh, building_collider, has_collider) in chunk_query.iter() {
+        let coord = chunk.coord;
+
+        if !keep.contains(&coord) {
+            if has_collider || building_collider {
+                commands.entity(entity).remove::<(Collider, ChunkColliderTask)>();
+            }
+        } else if !has_collider {
+            if occupied.contains(&coord) {
+                commands.entity(entity)
+                    .insert(build_chunk_collider(&heightfield, coord))
+                    .remove::<ChunkColliderTask>();
+            } else if !building_collider && in_range.contains(&coord) {
+                let heightfield = heightfield.clone();
+                let task = pool.spawn(async move { build_chunk_collider(&heightfield, coord) });
+                commands.entity(entity).insert(ChunkColliderTask(task));
+            }
+        }
+
+        let lod = lods[&coord];
+        let seams = CHUNK_SIDES.map(|side| lods.get(&(coord + side)).map_or(lod, |&neighbour| neighbour.max(lod)));
+        if !building_mesh && chunk.built != Some((lod, seams)) {
+            let heightfield = heightfield.clone();
+            let task = pool.spawn(async move { build_chunk_mesh(&heightfield, coord, lod, seams) });
+            commands.entity(entity).insert(ChunkMeshTask { task, lod, seams });
+        }
+    }
+}
+
+pub fn finish_terrain_chunk_tasks(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut mesh_tasks: Query<(Entity, &mut TerrainChunk, &mut ChunkMeshTask)>,
+    mut collider_tasks: Query<(Entity, &mut ChunkColliderTask)>,
+) {
+    for (entity, mut chunk, mut mesh_task) in mesh_tasks.iter_mut() {
+        if let Some(mesh) = block_on(future::poll_once(&mut mesh_task.task)) {
+            chunk.built = Some((mesh_task.lod, mesh_task.seams));
+            commands.entity(entity)
+                .insert(meshes.add(mesh))
+                .remove::<ChunkMeshTask>();
+        }
+    }
+
+    for (entity, mut collider_task) in collider_tasks.iter_mut() {
+        if let Some(collider) = block_on(future::poll_once(&mut collider_task.0)) {
+            commands.entity(entity)
+                .insert(collider)
+                .remove::<ChunkColliderTask>();
+        }
+    }
 }
 
 pub fn toggle_terrain_texture(
@@ -197,3 +539,34 @@ pub fn toggle_terrain_texture(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOLERANCE: f32 = 1e-3;
+
+    #[test]
+    fn height_matches_finest_chunk_grid() {
+        let heightfield = TerrainHeightfield::default();
+        let coord = chunk_coord(Vec2::new(120.0, -340.0));
+        let resolution = CHUNK_LOD_RESOLUTIONS[0];
+        let grid = build_chunk_grid(&heightfield, coord, resolution, [resolution; 4]);
+        assert!(!grid.triangles.is_empty());
+
+        for position in &grid.positions {
+            let height = heightfield.height_at(position.x, position.z);
+            assert!((height - (position.y + Y_OFFSET)).abs() < TOLERANCE, "vertex at {}", position);
+        }
+
+        // Points inside each triangle lie on the plane through its corners
+        for triangle in &grid.triangles {
+            let [a, b, c] = triangle.map(|index| grid.positions[index as usize]);
+            for weights in [Vec3::splat(1.0 / 3.0), Vec3::new(0.6, 0.3, 0.1), Vec3::new(0.1, 0.1, 0.8)] {
+                let point = a * weights.x + b * weights.y + c * weights.z;
+                let height = heightfield.height_at(point.x, point.z);
+                assert!((height - (point.y + Y_OFFSET)).abs() < TOLERANCE, "point at {}", point);
+            }
+        }
+    }
+}
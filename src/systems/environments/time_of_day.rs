@@ -0,0 +1,239 @@
+use bevy::prelude::*;
+use std::f32::consts::TAU;
+use crate::systems::core::screenplay::{MessageDisplay, display_message};
+
+// Clock
+const START_HOUR: f32 = 21.0;  // The base starts at night
+const HOURS_PER_SECOND: f32 = 1.0 / 60.0;  // A full day takes 24 minutes at normal speed
+const TIME_SCALES: &[f32] = &[1.0, 10.0, 100.0, 0.0];  // Cycled with L, 0 stops the clock
+
+// One point in the day. Everything between two keyframes is blended, except the lanterns,
+// which stay as they are until the next keyframe switches them.
+#[derive(Clone, Copy, Debug)]
+pub struct DayKeyframe {
+    pub name: &'static str,
+    pub hour: f32,
+    pub sun_elevation: f32,  // Degrees above the horizon; the moon stands in at night
+    pub sun_illuminance: f32,
+    pub sun_color: Color,
+    pub ambient_brightness: f32,  // Multiplier on the indoor and outdoor ambient levels
+    pub ambient_color: Color,
+    pub sky_tint: Color,
+    pub sentry_vision: f32,  // Multiplier on how far sentries see
+    pub lanterns: f32,  // 1 when the lanterns are fully lit
+}
+
+// Keyframes the day starts with, in order of hour. `TimeOfDay::keyframes` can be replaced
+// at runtime.
+pub const DEFAULT_DAY_KEYFRAMES: &[DayKeyframe] = &[
+    DayKeyframe {
+        name: "NIGHT",
+        hour: 0.0,
+        sun_elevation: 35.0,
+        sun_illuminance: 10.0,
+        sun_color: Color::srgb(0.2, 0.2, 0.3),
+        ambient_brightness: 1.0,
+        ambient_color: Color::srgb(0.1, 0.1, 0.3),
+        sky_tint: Color::srgb(0.5, 0.5, 0.7),
+        sentry_vision: 0.5,
+        lanterns: 1.0,
+    },
+    DayKeyframe {
+        name: "DAWN",
+        hour: 6.0,
+        sun_elevation: 5.0,
+        sun_illuminance: 300.0,
+        sun_color: Color::srgb(1.0, 0.55, 0.35),
+        ambient_brightness: 1.4,
+        ambient_color: Color::srgb(0.4, 0.3, 0.4),
+        sky_tint: Color::srgb(1.0, 0.7, 0.6),
+        sentry_vision: 0.7,
+        lanterns: 1.0,
+    },
+    DayKeyframe {
+        name: "MORNING",
+        hour: 9.0,
+        sun_elevation: 30.0,
+        sun_illuminance: 3000.0,
+        sun_color: Color::srgb(1.0, 0.9, 0.8),
+        ambient_brightness: 2.0,
+        ambient_color: Color::srgb(0.5, 0.55, 0.7),
+        sky_tint: Color::srgb(0.8, 0.9, 1.0),
+        sentry_vision: 1.0,
+        lanterns: 0.0,
+    },
+    DayKeyframe {
+        name: "NOON",
+        hour: 13.0,
+        sun_elevation: 60.0,
+        sun_illuminance: 6000.0,
+        sun_color: Color::srgb(1.0, 1.0, 0.95),
+        ambient_brightness: 2.5,
+        ambient_color: Color::srgb(0.6, 0.65, 0.8),
+        sky_tint: Color::srgb(0.9, 0.95, 1.0),
+        sentry_vision: 1.0,
+        lanterns: 0.0,
+    },
+    DayKeyframe {
+        name: "DUSK",
+        hour: 18.0,
+        sun_elevation: 8.0,
+        sun_illuminance: 500.0,
+        sun_color: Color::srgb(1.0, 0.5, 0.25),
+        ambient_brightness: 1.5,
+        ambient_color: Color::srgb(0.5, 0.35, 0.4),
+        sky_tint: Color::srgb(1.0, 0.6, 0.5),
+        sentry_vision: 0.75,
+        lanterns: 1.0,
+    },
+    DayKeyframe {
+        name: "NIGHTFALL",
+        hour: 20.0,
+        sun_elevation: 35.0,
+        sun_illuminance: 10.0,
+        sun_color: Color::srgb(0.2, 0.2, 0.3),
+        ambient_brightness: 1.0,
+        ambient_color: Color::srgb(0.1, 0.1, 0.3),
+        sky_tint: Color::srgb(0.5, 0.5, 0.7),
+        sentry_vision: 0.5,
+        lanterns: 1.0,
+    },
+];
+
+// The sun (or moon) that the time of day moves
+#[derive(Component)]
+pub struct Sun;
+
+#[derive(Component)]
+pub struct SkyDome;
+
+// The light the time of day is giving right now
+#[derive(Clone, Copy, Debug)]
+pub struct Daylight {
+    pub sun_elevation: f32,
+    pub sun_illuminance: f32,
+    pub sun_color: Color,
+    pub ambient_brightness: f32,
+    pub ambient_color: Color,
+    pub sky_tint: Color,
+    pub sentry_vision: f32,
+    pub lanterns: f32,
+}
+
+impl Daylight {
+    fn between(from: &DayKeyframe, to: &DayKeyframe, t: f32) -> Self {
+        let mix = |a: f32, b: f32| a + (b - a) * t;
+        Self {
+            sun_elevation: mix(from.sun_elevation, to.sun_elevation),
+            sun_illuminance: mix(from.sun_illuminance, to.sun_illuminance),
+            sun_color: from.sun_color.mix(&to.sun_color, t),
+            ambient_brightness: mix(from.ambient_brightness, to.ambient_brightness),
+            ambient_color: from.ambient_color.mix(&to.ambient_color, t),
+            sky_tint: from.sky_tint.mix(&to.sky_tint, t),
+            sentry_vision: mix(from.sentry_vision, to.sentry_vision),
+            lanterns: from.lanterns,  // Lanterns switch on and off rather than dimming over hours
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct TimeOfDay {
+    pub hour: f32,  // 0 to 24
+    pub time_scale: f32,  // Multiplier on how fast the clock runs, 0 to stop it
+    pub keyframes: Vec<DayKeyframe>,  // In order of hour, at least one
+}
+
+impl Default for TimeOfDay {
+    fn default() -> Self {
+        Self {
+            hour: START_HOUR,
+            time_scale: TIME_SCALES[0],
+            keyframes: DEFAULT_DAY_KEYFRAMES.to_vec(),
+        }
+    }
+}
+
+impl TimeOfDay {
+    // Index of the keyframe at or before the current hour
+    fn keyframe_index(&self) -> usize {
+        self.keyframes.iter().rposition(|keyframe| keyframe.hour <= self.hour).unwrap_or(self.keyframes.len() - 1)
+    }
+
+    pub fn daylight(&self) -> Daylight {
+        let index = self.keyframe_index();
+        let from = &self.keyframes[index];
+        let to = &self.keyframes[(index + 1) % self.keyframes.len()];
+
+        // The last keyframe blends round into the first one after midnight
+        let span = (to.hour - from.hour).rem_euclid(24.0);
+        let t = if span > 0.0 { (self.hour - from.hour).rem_euclid(24.0) / span } else { 0.0 };
+        Daylight::between(from, to, t)
+    }
+
+    pub fn sentry_vision(&self) -> f32 {
+        self.daylight().sentry_vision
+    }
+
+    // Jump ahead to the next keyframe, returning its name
+    pub fn skip_to_next_keyframe(&mut self) -> &'static str {
+        let next = &self.keyframes[(self.keyframe_index() + 1) % self.keyframes.len()];
+        self.hour = next.hour;
+        next.name
+    }
+}
+
+// Run the clock, move the sun and tint the sky. The sun's light itself is set by
+// `apply_lighting`.
+pub fn update_time_of_day(
+    mut time_of_day: ResMut<TimeOfDay>,
+    mut sun_query: Query<&mut Transform, With<Sun>>,
+    sky_query: Query<&Handle<StandardMaterial>, With<SkyDome>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    time: Res<Time>,
+) {
+    let advance = time.delta_seconds() * HOURS_PER_SECOND * time_of_day.time_scale;
+    time_of_day.hour = (time_of_day.hour + advance).rem_euclid(24.0);
+    let daylight = time_of_day.daylight();
+
+    // The sun swings round once a day
+    let azimuth = time_of_day.hour / 24.0 * TAU;
+    for mut transform in sun_query.iter_mut() {
+        transform.rotation = Quat::from_euler(
+            EulerRot::ZYX,
+            0.0,
+            azimuth,
+            -daylight.sun_elevation.to_radians(),
+        );
+    }
+
+    for handle in sky_query.iter() {
+        if materials.get(handle).is_some_and(|material| material.base_color != daylight.sky_tint) {
+            if let Some(material) = materials.get_mut(handle) {
+                material.base_color = daylight.sky_tint;
+            }
+        }
+    }
+}
+
+// K skips to the next part of the day, L changes how fast the clock runs
+pub fn time_of_day_controls(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut time_of_day: ResMut<TimeOfDay>,
+    mut message_display: ResMut<MessageDisplay>,
+) {
+    if keyboard_input.just_pressed(KeyCode::KeyK) {
+        let name = time_of_day.skip_to_next_keyframe();
+        display_message(name, Color::WHITE, &mut message_display);
+    }
+
+    if keyboard_input.just_pressed(KeyCode::KeyL) {
+        let current = TIME_SCALES.iter().position(|&scale| scale == time_of_day.time_scale).unwrap_or(0);
+        time_of_day.time_scale = TIME_SCALES[(current + 1) % TIME_SCALES.len()];
+        let message = if time_of_day.time_scale == 0.0 {
+            "TIME STOPPED".to_string()
+        } else {
+            format!("TIME x{}", time_of_day.time_scale)
+        };
+        display_message(message, Color::WHITE, &mut message_display);
+    }
+}
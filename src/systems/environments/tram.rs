@@ -1,20 +1,404 @@
 use bevy::prelude::*;
-use crate::systems::core::setup::TramCar;
+use avian3d::prelude::*;
+use crate::components::Protagonist;
+use crate::systems::core::interaction::{Interactable, InteractEvent};
+use crate::systems::core::screenplay::{MessageDisplay, display_message};
+use crate::systems::environments::terrain::TerrainHeightfield;
+use crate::systems::environments::surface::SurfaceMaterial;
 
-pub fn move_tram(
+// Stations (x, z) in the order the line visits them; the line loops back to the first
+// and runs along the terrain
+const TRAM_STATIONS: &[(&str, Vec2)] = &[
+    ("START", Vec2::new(260.0, -120.0)),
+    ("AIRLOCK", Vec2::new(430.0, -500.0)),
+    ("GARAGE", Vec2::new(1800.0, -950.0)),
+    ("REACTOR", Vec2::new(-910.0, 1250.0)),
+];
+
+// Line
+const SAMPLES_PER_SPAN: usize = 160;  // Polyline points between two stations
+const RAIL_GAUGE: f32 = 5.0;
+const RAIL_SIZE: f32 = 0.5;
+const RAIL_HEIGHT: f32 = 0.5;  // Above the ground
+
+// Cars
+const TRAM_CAR_COUNT: usize = 2;
+const TRAM_CAR_SIZE: Vec3 = Vec3::new(8.0, 7.0, 24.0);  // Width, height, length
+const TRAM_SPEED: f32 = 60.0;
+const TRAM_MIN_SPEED_FRACTION: f32 = 0.15;  // Creep speed right before a stop
+const TRAM_BRAKE_DISTANCE: f32 = 80.0;
+const TRAM_DWELL_TIME: f32 = 10.0;
+const TRAM_BOARD_RANGE: f32 = 12.0;
+const TRAM_ROOF_OFFSET: f32 = TRAM_CAR_SIZE.y / 2.0 + 1.0;
+const TRAM_DISMOUNT_OFFSET: f32 = TRAM_CAR_SIZE.x;
+
+// Stations
+const PLATFORM_SIZE: Vec3 = Vec3::new(10.0, 1.0, 30.0);
+
+// The rail line as an evenly sampled closed polyline
+#[derive(Resource)]
+pub struct TramLine {
+    points: Vec<Vec3>,
+    distances: Vec<f32>,  // Distance along the line to each point
+    station_distances: Vec<f32>,
+    length: f32,
+}
+
+impl TramLine {
+    fn new(stations: &[Vec2], heightfield: &TerrainHeightfield) -> Self {
+        let count = stations.len();
+        let mut points = Vec::with_capacity(count * SAMPLES_PER_SPAN + 1);
+        for i in 0..count {
+            let p0 = stations[(i + count - 1) % count];
+            let p1 = stations[i];
+            let p2 = stations[(i + 1) % count];
+            let p3 = stations[(i + 2) % count];
+            for sample in 0..SAMPLES_PER_SPAN {
+                points.push(catmull_rom(p0, p1, p2, p3, sample as f32 / SAMPLES_PER_SPAN as f32));
+            }
+        }
+        points.push(stations[0]);  // Close the loop
+
+        let points: Vec<Vec3> = points.into_iter()
+            .map(|point| Vec3::new(point.x, heightfield.height_at(point.x, point.y), point.y))
+            .collect();
+
+        let mut distances = vec![0.0];
+        for window in points.windows(2) {
+            distances.push(distances[distances.len() - 1] + window[0].distance(window[1]));
+        }
+
+        let station_distances = (0..count).map(|i| distances[i * SAMPLES_PER_SPAN]).collect();
+        let length = distances[distances.len() - 1];
+
+        Self { points, distances, station_distances, length }
+    }
+
+    // Position and direction of travel at `distance` along the line
+    pub fn sample(&self, distance: f32) -> (Vec3, Vec3) {
+        let distance = distance.rem_euclid(self.length);
+        let index = self.distances.partition_point(|&d| d <= distance).clamp(1, self.points.len() - 1);
+        let (start, end) = (self.points[index - 1], self.points[index]);
+        let span = self.distances[index] - self.distances[index - 1];
+        let t = if span > 0.0 { (distance - self.distances[index - 1]) / span } else { 0.0 };
+        (start.lerp(end, t), (end - start).normalize_or_zero())
+    }
+
+    // How far ahead `target` is when travelling forward from `distance`
+    fn distance_ahead(&self, distance: f32, target: f32) -> f32 {
+        (target - distance).rem_euclid(self.length)
+    }
+}
+
+fn catmull_rom(p0: Vec2, p1: Vec2, p2: Vec2, p3: Vec2, t: f32) -> Vec2 {
+    let t2 = t * t;
+    let t3 = t2 * t;
+    0.5 * ((2.0 * p1)
+        + (p2 - p0) * t
+        + (2.0 * p0 - 5.0 * p1 + 4.0 * p2 - p3) * t2
+        + (3.0 * p1 - p0 - 3.0 * p2 + p3) * t3)
+}
+
+#[derive(Component)]
+pub struct TramCar {
+    pub distance: f32,  // How far along the line
+    pub next_station: usize,
+    pub dwell: Timer,  // Time left standing at the last station
+}
+
+impl TramCar {
+    pub fn is_moving(&self) -> bool {
+        self.dwell.finished()
+    }
+}
+
+// Where the protagonist can get on a car: through the doors or up onto the roof
+#[derive(Component)]
+pub struct TramBoardPoint {
+    pub car: Entity,
+    pub on_roof: bool,
+}
+
+// On the protagonist while they ride a car
+#[derive(Component)]
+pub struct TramRider {
+    pub car: Entity,
+    pub on_roof: bool,
+    pub moving: bool,
+}
+
+pub fn spawn_tram_network(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    heightfield: Res<TerrainHeightfield>,
+) {
+    let stations: Vec<Vec2> = TRAM_STATIONS.iter().map(|(_, position)| *position).collect();
+    let line = TramLine::new(&stations, &heightfield);
+
+    // Rails, one stretched unit cube per polyline segment and side
+    let rail_mesh = meshes.add(Cuboid::new(1.0, 1.0, 1.0));
+    let rail_material = materials.add(StandardMaterial {
+        base_color: Color::srgb(0.3, 0.3, 0.35),
+        metallic: 0.9,
+        perceptual_roughness: 0.3,
+        ..default()
+    });
+
+    for window in line.points.windows(2) {
+        let (start, end) = (window[0], window[1]);
+        let length = start.distance(end);
+        if length <= f32::EPSILON {
+            continue;
+        }
+
+        let center = (start + end) / 2.0 + Vec3::Y * RAIL_HEIGHT;
+        let rotation = Transform::from_translation(center).looking_at(end + Vec3::Y * RAIL_HEIGHT, Vec3::Y).rotation;
+        for side in [-0.5, 0.5] {
+            commands.spawn(PbrBundle {
+                mesh: rail_mesh.clone(),
+                material: rail_material.clone(),
+                transform: Transform::from_translation(center + rotation * Vec3::X * RAIL_GAUGE * side)
+                    .with_rotation(rotation)
+                    .with_scale(Vec3::new(RAIL_SIZE, RAIL_SIZE, length)),
+                ..default()
+            });
+        }
+    }
+
+    // Platforms beside the track with a lamp so stations are easy to spot
+    let platform_mesh = meshes.add(Cuboid::new(PLATFORM_SIZE.x, PLATFORM_SIZE.y, PLATFORM_SIZE.z));
+    let platform_material = materials.add(StandardMaterial {
+        base_color: Color::srgb(0.5, 0.5, 0.5),
+        perceptual_roughness: 0.9,
+        ..default()
+    });
+
+    for (index, (name, _)) in TRAM_STATIONS.iter().enumerate() {
+        let (position, direction) = line.sample(line.station_distances[index]);
+        let side = direction.cross(Vec3::Y).normalize_or_zero();
+        let platform_position = position + side * (TRAM_CAR_SIZE.x / 2.0 + PLATFORM_SIZE.x / 2.0 + 1.0);
+
+        commands.spawn((
+            RigidBody::Static,
+            Collider::cuboid(PLATFORM_SIZE.x, PLATFORM_SIZE.y, PLATFORM_SIZE.z),
+            SurfaceMaterial::Metal,
+            PbrBundle {
+                mesh: platform_mesh.clone(),
+                material: platform_material.clone(),
+                transform: Transform::from_translation(platform_position)
+                    .looking_to(direction, Vec3::Y),
+                ..default()
+            },
+            Name::new(format!("TramStation {}", name)),
+        ));
+
+        commands.spawn(PointLightBundle {
+            point_light: PointLight {
+                color: Color::srgb(1.0, 0.85, 0.5),
+                intensity: 2000000.0,
+                range: 60.0,
+                ..default()
+            },
+            transform: Transform::from_translation(platform_position + Vec3::Y * 12.0),
+            ..default()
+        });
+    }
+
+    // Cars, spread evenly over the stations
+    let car_mesh = meshes.add(Cuboid::new(TRAM_CAR_SIZE.x, TRAM_CAR_SIZE.y, TRAM_CAR_SIZE.z));
+    let car_material = materials.add(StandardMaterial {
+        base_color: Color::srgb(0.8, 0.75, 0.2),
+        emissive: Color::srgb(0.1, 0.08, 0.0).into(),
+        metallic: 0.6,
+        perceptual_roughness: 0.4,
+        ..default()
+    });
+
+    for car_index in 0..TRAM_CAR_COUNT {
+        let station = car_index * TRAM_STATIONS.len() / TRAM_CAR_COUNT;
+        let distance = line.station_distances[station];
+        let (position, direction) = line.sample(distance);
+
+        let car = commands.spawn((
+            RigidBody::Kinematic,
+            Collider::cuboid(TRAM_CAR_SIZE.x, TRAM_CAR_SIZE.y, TRAM_CAR_SIZE.z),
+            SurfaceMaterial::Metal,
+            PbrBundle {
+                mesh: car_mesh.clone(),
+                material: car_material.clone(),
+                transform: Transform::from_translation(position + Vec3::Y * (RAIL_HEIGHT + TRAM_CAR_SIZE.y / 2.0))
+                    .looking_to(direction, Vec3::Y),
+                ..default()
+            },
+            TramCar {
+                distance,
+                next_station: (station + 1) % TRAM_STATIONS.len(),
+                dwell: Timer::from_seconds(TRAM_DWELL_TIME, TimerMode::Once),
+            },
+            Name::new("TramCar"),
+        )).id();
+
+        commands.entity(car).with_children(|parent| {
+            parent.spawn((
+                SpatialBundle::from_transform(Transform::from_xyz(TRAM_CAR_SIZE.x / 2.0, -TRAM_CAR_SIZE.y / 4.0, 0.0)),
+                TramBoardPoint { car, on_roof: false },
+                Interactable::new("board the tram", TRAM_BOARD_RANGE),
+            ));
+            parent.spawn((
+                SpatialBundle::from_transform(Transform::from_xyz(-TRAM_CAR_SIZE.x / 2.0, 0.0, TRAM_CAR_SIZE.z / 2.0)),
+                TramBoardPoint { car, on_roof: true },
+                Interactable::new("climb onto the tram roof", TRAM_BOARD_RANGE),
+            ));
+        });
+    }
+
+    commands.insert_resource(line);
+}
+
+// Run each car along the line, slowing into stations and waiting there
+pub fn move_trams(
+    line: Res<TramLine>,
+    mut car_query: Query<(Entity, &mut Transform, &mut TramCar)>,
+    rider_query: Query<&TramRider>,
+    mut message_display: ResMut<MessageDisplay>,
     time: Res<Time>,
-    mut query: Query<(&mut Transform, &mut TramCar)>,
 ) {
-    for (mut transform, mut tram) in query.iter_mut() {
-        tram.time += time.delta_seconds();
-        
-        // Calculate the offset using a sine wave
-        let offset = tram.amplitude * (tram.time * tram.frequency).sin();
-        
-        // Distribute the offset equally between X and Z to create 45-degree movement
-        let offset_x = offset * 0.707; // cos(45°) ≈ 0.707
-        let offset_z = offset * 0.707; // sin(45°) ≈ 0.707
-        
-        transform.translation = tram.origin + Vec3::new(offset_x, 0.0, offset_z);
+    for (entity, mut transform, mut car) in car_query.iter_mut() {
+        if !car.is_moving() {
+            car.dwell.tick(time.delta());
+            continue;
+        }
+
+        let stop = line.station_distances[car.next_station];
+        let remaining = line.distance_ahead(car.distance, stop);
+        let speed_fraction = (remaining / TRAM_BRAKE_DISTANCE).clamp(TRAM_MIN_SPEED_FRACTION, 1.0);
+        let step = TRAM_SPEED * speed_fraction * time.delta_seconds();
+
+        if step >= remaining {
+            car.distance = stop;
+            car.dwell.reset();
+
+            if rider_query.iter().any(|rider| rider.car == entity) {
+                let (name, _) = TRAM_STATIONS[car.next_station];
+                display_message(format!("{} STATION", name), Color::srgb(1.0, 0.85, 0.5), &mut message_display);
+            }
+            car.next_station = (car.next_station + 1) % TRAM_STATIONS.len();
+        } else {
+            car.distance = (car.distance + step).rem_euclid(line.length);
+        }
+
+        let (position, direction) = line.sample(car.distance);
+        transform.translation = position + Vec3::Y * (RAIL_HEIGHT + TRAM_CAR_SIZE.y / 2.0);
+        if direction != Vec3::ZERO {
+            transform.look_to(direction, Vec3::Y);
+        }
+    }
+}
+
+// Keep riders in their seat or on the roof as the car moves
+pub fn carry_tram_riders(
+    car_query: Query<(&TramCar, &Transform), Without<TramRider>>,
+    mut rider_query: Query<(&mut TramRider, &mut Transform, &mut LinearVelocity)>,
+) {
+    for (mut rider, mut transform, mut velocity) in rider_query.iter_mut() {
+        let Ok((car, car_transform)) = car_query.get(rider.car) else {
+            continue;
+        };
+
+        let offset = if rider.on_roof { Vec3::Y * TRAM_ROOF_OFFSET } else { Vec3::ZERO };
+        transform.translation = car_transform.translation + offset;
+        transform.rotation = car_transform.rotation;
+        velocity.0 = Vec3::ZERO;
+        rider.moving = car.is_moving();
+    }
+}
+
+pub fn handle_tram_interaction(
+    mut commands: Commands,
+    mut interact_events: EventReader<InteractEvent>,
+    board_query: Query<&TramBoardPoint>,
+    car_query: Query<(&TramCar, &Transform)>,
+    mut protagonist_query: Query<(Entity, &mut Transform, &Protagonist, Option<&TramRider>), Without<TramCar>>,
+    mut message_display: ResMut<MessageDisplay>,
+) {
+    for event in interact_events.read() {
+        let Ok(board_point) = board_query.get(event.target) else {
+            continue;
+        };
+        let Ok((car, _)) = car_query.get(board_point.car) else {
+            continue;
+        };
+        let Ok((protagonist_entity, mut transform, protagonist, rider)) = protagonist_query.get_single_mut() else {
+            continue;
+        };
+
+        // Getting off: step down beside the rider's own car, only through its doors once it has stopped
+        if let Some(rider) = rider {
+            if board_point.car != rider.car {
+                continue;
+            }
+            let Ok((rider_car, rider_car_transform)) = car_query.get(rider.car) else {
+                continue;
+            };
+            if !rider.on_roof && rider_car.is_moving() {
+                display_message("THE DOORS ARE CLOSED", Color::srgb(1.0, 0.85, 0.5), &mut message_display);
+                continue;
+            }
+
+            transform.translation = rider_car_transform.translation
+                + rider_car_transform.right().as_vec3() * TRAM_DISMOUNT_OFFSET
+                + Vec3::Y * 2.0;
+            commands.entity(protagonist_entity)
+                .remove::<TramRider>()
+                .insert((
+                    RigidBody::Dynamic,
+                    Collider::cuboid(1.0, 0.25, 1.0),
+                    Visibility::Inherited,
+                ));
+            continue;
+        }
+
+        if protagonist.is_driving || protagonist.is_dirigible {
+            continue;
+        }
+        if !board_point.on_roof && car.is_moving() {
+            display_message("THE DOORS ARE CLOSED", Color::srgb(1.0, 0.85, 0.5), &mut message_display);
+            continue;
+        }
+
+        // Riders inside are out of sight; on the roof they're exposed but carried along
+        let visibility = if board_point.on_roof { Visibility::Inherited } else { Visibility::Hidden };
+        commands.entity(protagonist_entity)
+            .insert((
+                TramRider {
+                    car: board_point.car,
+                    on_roof: board_point.on_roof,
+                    moving: car.is_moving(),
+                },
+                RigidBody::Kinematic,
+                visibility,
+            ))
+            .remove::<Collider>();
+    }
+}
+
+pub fn update_tram_prompts(
+    mut board_query: Query<(&TramBoardPoint, &mut Interactable)>,
+    rider_query: Query<&TramRider>,
+) {
+    let riding = rider_query.get_single().ok();
+    for (board_point, mut interactable) in board_query.iter_mut() {
+        let prompt = if riding.map_or(false, |rider| rider.car == board_point.car) {
+            "get off the tram"
+        } else if board_point.on_roof {
+            "climb onto the tram roof"
+        } else {
+            "board the tram"
+        };
+
+        if interactable.prompt != prompt {
+            interactable.prompt = prompt.to_string();
+        }
     }
 }
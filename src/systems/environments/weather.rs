@@ -0,0 +1,305 @@
+use bevy::prelude::*;
+use crate::components::Protagonist;
+use crate::systems::core::minimap::MinimapCamera;
+use crate::systems::core::screenplay::{MessageDisplay, display_message};
+use crate::systems::environments::wind::WindField;
+
+// Weather changes
+const WEATHER_TRANSITION_TIME: f32 = 20.0;  // Seconds to blend from one state into the next
+const WEATHER_FOG_COLOR: Color = Color::srgb(0.75, 0.8, 0.88);
+
+// Snowfall
+const SNOWFLAKE_COUNT: usize = 800;  // Flakes at full snowfall
+const SNOWFLAKE_SIZE: f32 = 0.15;
+const SNOWFLAKE_FALL_SPEED: f32 = 6.0;
+const SNOWFLAKE_SPEED_VARIANCE: f32 = 0.5;  // Fraction either way, so flakes don't fall in sheets
+const SNOWFALL_HALF_EXTENT: Vec3 = Vec3::new(60.0, 40.0, 60.0);  // Box of snow kept around the protagonist
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum WeatherState {
+    Clear,
+    LightSnow,
+    Blizzard,
+    Whiteout,
+}
+
+// Everything a weather state drives
+#[derive(Clone, Copy, Debug)]
+pub struct WeatherProfile {
+    pub fog_start: f32,
+    pub fog_end: f32,  // Nothing is visible past this
+    pub snowfall: f32,  // 0 to 1
+    pub wind_strength: f32,  // Multiplier on the wind's base speed
+    pub sentry_vision: f32,  // Multiplier on how far sentries see
+    pub minimap_range: f32,  // How far out sentries still show on the minimap
+    pub footprint_ageing: f32,  // How fast falling snow fills in tracks
+}
+
+impl WeatherProfile {
+    fn lerp(self, other: Self, t: f32) -> Self {
+        let mix = |a: f32, b: f32| a + (b - a) * t;
+        Self {
+            fog_start: mix(self.fog_start, other.fog_start),
+            fog_end: mix(self.fog_end, other.fog_end),
+            snowfall: mix(self.snowfall, other.snowfall),
+            wind_strength: mix(self.wind_strength, other.wind_strength),
+            sentry_vision: mix(self.sentry_vision, other.sentry_vision),
+            minimap_range: mix(self.minimap_range, other.minimap_range),
+            footprint_ageing: mix(self.footprint_ageing, other.footprint_ageing),
+        }
+    }
+}
+
+impl WeatherState {
+    pub fn profile(self) -> WeatherProfile {
+        match self {
+            WeatherState::Clear => WeatherProfile {
+                fog_start: 2000.0,
+                fog_end: 8000.0,
+                snowfall: 0.0,
+                wind_strength: 1.0,
+                sentry_vision: 1.0,
+                minimap_range: 5000.0,
+                footprint_ageing: 1.0,
+            },
+            WeatherState::LightSnow => WeatherProfile {
+                fog_start: 500.0,
+                fog_end: 2500.0,
+                snowfall: 0.3,
+                wind_strength: 1.5,
+                sentry_vision: 0.8,
+                minimap_range: 2000.0,
+                footprint_ageing: 2.0,
+            },
+            WeatherState::Blizzard => WeatherProfile {
+                fog_start: 50.0,
+                fog_end: 600.0,
+                snowfall: 0.8,
+                wind_strength: 3.0,
+                sentry_vision: 0.4,
+                minimap_range: 600.0,
+                footprint_ageing: 5.0,
+            },
+            WeatherState::Whiteout => WeatherProfile {
+                fog_start: 0.0,
+                fog_end: 150.0,
+                snowfall: 1.0,
+                wind_strength: 4.0,
+                sentry_vision: 0.15,
+                minimap_range: 150.0,
+                footprint_ageing: 8.0,
+            },
+        }
+    }
+
+    // Shortest and longest time the weather stays in this state
+    fn duration(self) -> (f32, f32) {
+        match self {
+            WeatherState::Clear => (120.0, 240.0),
+            WeatherState::LightSnow => (60.0, 150.0),
+            WeatherState::Blizzard => (45.0, 90.0),
+            WeatherState::Whiteout => (20.0, 45.0),
+        }
+    }
+
+    // Weather only ever moves one step at a time
+    fn next(self) -> Self {
+        let worsens = fastrand::bool();
+        match self {
+            WeatherState::Clear => WeatherState::LightSnow,
+            WeatherState::LightSnow if worsens => WeatherState::Blizzard,
+            WeatherState::LightSnow => WeatherState::Clear,
+            WeatherState::Blizzard if worsens => WeatherState::Whiteout,
+            WeatherState::Blizzard => WeatherState::LightSnow,
+            WeatherState::Whiteout => WeatherState::Blizzard,
+        }
+    }
+
+    fn random_duration(self) -> f32 {
+        let (min, max) = self.duration();
+        min + fastrand::f32() * (max - min)
+    }
+}
+
+// The weather right now, blending from the previous state into the current one
+#[derive(Resource)]
+pub struct Weather {
+    pub state: WeatherState,
+    previous: WeatherState,
+    blend: f32,  // 0 when the state has just changed, 1 once it has settled
+    timer: Timer,
+}
+
+impl Default for Weather {
+    fn default() -> Self {
+        Self {
+            state: WeatherState::Clear,
+            previous: WeatherState::Clear,
+            blend: 1.0,
+            timer: Timer::from_seconds(WeatherState::Clear.random_duration(), TimerMode::Once),
+        }
+    }
+}
+
+impl Weather {
+    pub fn profile(&self) -> WeatherProfile {
+        self.previous.profile().lerp(self.state.profile(), self.blend)
+    }
+
+    pub fn sentry_vision(&self) -> f32 {
+        self.profile().sentry_vision
+    }
+
+    pub fn minimap_range(&self) -> f32 {
+        self.profile().minimap_range
+    }
+
+    pub fn footprint_ageing(&self) -> f32 {
+        self.profile().footprint_ageing
+    }
+
+    // Jump straight to a state, e.g. for a scripted storm
+    pub fn set(&mut self, state: WeatherState) {
+        self.previous = self.state;
+        self.state = state;
+        self.blend = 0.0;
+        self.timer = Timer::from_seconds(state.random_duration(), TimerMode::Once);
+    }
+}
+
+#[derive(Component)]
+pub struct Snowflake {
+    index: usize,  // Flakes past the current snowfall's share are hidden
+    fall_speed: f32,
+}
+
+// Move the weather on and let it drive the wind
+pub fn update_weather(
+    mut weather: ResMut<Weather>,
+    mut wind: ResMut<WindField>,
+    mut message_display: ResMut<MessageDisplay>,
+    time: Res<Time>,
+) {
+    weather.blend = (weather.blend + time.delta_seconds() / WEATHER_TRANSITION_TIME).min(1.0);
+
+    if weather.timer.tick(time.delta()).just_finished() {
+        let next = weather.state.next();
+        weather.set(next);
+
+        match next {
+            WeatherState::Blizzard if weather.previous == WeatherState::LightSnow => display_message(
+                "A BLIZZARD IS COMING IN - SENTRIES WON'T SEE FAR",
+                Color::srgb(0.7, 0.85, 1.0),
+                &mut message_display,
+            ),
+            WeatherState::Whiteout => display_message(
+                "WHITEOUT - CROSS THE TUNDRA WHILE THE SENTRIES ARE BLIND",
+                Color::srgb(0.7, 0.85, 1.0),
+                &mut message_display,
+            ),
+            WeatherState::Clear => display_message(
+                "THE SKIES ARE CLEARING",
+                Color::WHITE,
+                &mut message_display,
+            ),
+            _ => {}
+        }
+    }
+
+    wind.strength = weather.profile().wind_strength;
+}
+
+// Distance fog on the main camera closes in with the weather
+pub fn apply_weather_fog(
+    mut commands: Commands,
+    weather: Res<Weather>,
+    mut camera_query: Query<(Entity, Option<&mut FogSettings>), (With<Camera3d>, Without<MinimapCamera>)>,
+) {
+    let profile = weather.profile();
+    let falloff = FogFalloff::Linear {
+        start: profile.fog_start,
+        end: profile.fog_end,
+    };
+
+    for (entity, fog) in camera_query.iter_mut() {
+        match fog {
+            Some(mut fog) => fog.falloff = falloff.clone(),
+            None => {
+                commands.entity(entity).insert(FogSettings {
+                    color: WEATHER_FOG_COLOR,
+                    falloff: falloff.clone(),
+                    ..default()
+                });
+            }
+        }
+    }
+}
+
+pub fn spawn_snowfall(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let mesh = meshes.add(Cuboid::new(SNOWFLAKE_SIZE, SNOWFLAKE_SIZE, SNOWFLAKE_SIZE));
+    let material = materials.add(StandardMaterial {
+        base_color: Color::WHITE,
+        emissive: LinearRgba::rgb(0.5, 0.5, 0.6),
+        unlit: true,
+        ..default()
+    });
+
+    for index in 0..SNOWFLAKE_COUNT {
+        let offset = Vec3::new(
+            fastrand::f32() * 2.0 - 1.0,
+            fastrand::f32() * 2.0 - 1.0,
+            fastrand::f32() * 2.0 - 1.0,
+        ) * SNOWFALL_HALF_EXTENT;
+
+        commands.spawn((
+            PbrBundle {
+                mesh: mesh.clone(),
+                material: material.clone(),
+                transform: Transform::from_translation(offset),
+                visibility: Visibility::Hidden,
+                ..default()
+            },
+            Snowflake {
+                index,
+                fall_speed: SNOWFLAKE_FALL_SPEED * (1.0 + (fastrand::f32() * 2.0 - 1.0) * SNOWFLAKE_SPEED_VARIANCE),
+            },
+        ));
+    }
+}
+
+// Blow the snow about on the wind, wrapping it round a box that follows the protagonist
+pub fn update_snowfall(
+    weather: Res<Weather>,
+    wind: Res<WindField>,
+    protagonist_query: Query<&Transform, (With<Protagonist>, Without<Snowflake>)>,
+    mut snowflake_query: Query<(&Snowflake, &mut Transform, &mut Visibility)>,
+    time: Res<Time>,
+) {
+    let Ok(protagonist_transform) = protagonist_query.get_single() else {
+        return;
+    };
+
+    let center = protagonist_transform.translation;
+    let active = (weather.profile().snowfall * SNOWFLAKE_COUNT as f32) as usize;
+    let delta = time.delta_seconds();
+
+    for (snowflake, mut transform, mut visibility) in snowflake_query.iter_mut() {
+        let falling = snowflake.index < active;
+        let wanted = if falling { Visibility::Visible } else { Visibility::Hidden };
+        if *visibility != wanted {
+            *visibility = wanted;
+        }
+        if !falling {
+            continue;
+        }
+
+        let velocity = wind.sample(transform.translation) + Vec3::NEG_Y * snowflake.fall_speed;
+        let offset = transform.translation + velocity * delta - center;
+        let size = SNOWFALL_HALF_EXTENT * 2.0;
+        transform.translation = center + (offset + SNOWFALL_HALF_EXTENT).rem_euclid(size) - SNOWFALL_HALF_EXTENT;
+    }
+}
@@ -0,0 +1,56 @@
+use bevy::prelude::*;
+use noise::OpenSimplex;
+use noise::NoiseFn;
+use std::f32::consts::PI;
+
+// Wind Parameters
+const WIND_SEED: u32 = 7;
+const WIND_BASE_SPEED: f32 = 12.0;  // Average wind speed in units per second
+const WIND_SPATIAL_SCALE: f64 = 0.0005;  // How quickly the wind changes across the map
+const WIND_CHANGE_RATE: f64 = 0.05;  // How quickly the wind changes over time
+const WIND_VEER_RATE: f64 = 0.01;  // How quickly the prevailing direction swings round
+const WIND_DIRECTION_VARIANCE: f32 = PI / 4.0;  // Local deviation from the prevailing direction
+const WIND_GUST_VARIANCE: f32 = 0.6;  // Gusts scale the speed by up to this fraction either way
+const WIND_GUST_RATE: f64 = 0.4;
+
+// Direction and speed of the wind everywhere on the map, shared by anything it blows around
+#[derive(Resource)]
+pub struct WindField {
+    noise: OpenSimplex,
+    elapsed: f32,
+    pub strength: f32,  // Multiplier on the base speed, e.g. raised by a storm
+}
+
+impl Default for WindField {
+    fn default() -> Self {
+        Self {
+            noise: OpenSimplex::new(WIND_SEED),
+            elapsed: 0.0,
+            strength: 1.0,
+        }
+    }
+}
+
+impl WindField {
+    // Wind velocity at a point, in units per second. Wind only blows horizontally.
+    pub fn sample(&self, position: Vec3) -> Vec3 {
+        let time = self.elapsed as f64;
+        let x = position.x as f64 * WIND_SPATIAL_SCALE;
+        let z = position.z as f64 * WIND_SPATIAL_SCALE;
+
+        let prevailing = self.noise.get([time * WIND_VEER_RATE, 0.0]) as f32 * PI;
+        let deviation = self.noise.get([x, z, time * WIND_CHANGE_RATE]) as f32 * WIND_DIRECTION_VARIANCE;
+        let gust = self.noise.get([x + 100.0, z + 100.0, time * WIND_GUST_RATE]) as f32 * WIND_GUST_VARIANCE;
+
+        let angle = prevailing + deviation;
+        let speed = WIND_BASE_SPEED * self.strength * (1.0 + gust);
+        Vec3::new(angle.cos(), 0.0, angle.sin()) * speed
+    }
+}
+
+pub fn update_wind(
+    time: Res<Time>,
+    mut wind: ResMut<WindField>,
+) {
+    wind.elapsed += time.delta_seconds();
+}
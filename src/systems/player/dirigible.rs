@@ -1,122 +1,274 @@
 use bevy::prelude::*;
 use avian3d::prelude::*;
 use crate::components::Protagonist;
+use crate::systems::environments::wind::WindField;
+use crate::systems::core::interaction::{Interactable, InteractEvent};
+use crate::systems::core::screenplay::{MessageDisplay, display_message};
 
 // Movement constants
-pub const DIRIGIBLE_VERTICAL_SPEED: f32 = 100.0;
 pub const DIRIGIBLE_FORWARD_SPEED: f32 = 450.0;
 pub const DIRIGIBLE_TURN_SPEED: f32 = 0.6;
 pub const DIRIGIBLE_ACCELERATION: f32 = 75.0;
 pub const DIRIGIBLE_DECELERATION: f32 = 8.0;
 pub const DIRIGIBLE_DAMPENING: f32 = 0.1;
+pub const DIRIGIBLE_WIND_DRIFT: f32 = 3.0;  // The envelope catches a lot of wind
 
 // Movement limits
 pub const DIRIGIBLE_MAX_HORIZONTAL_SPEED: f32 = 500.0;
 pub const DIRIGIBLE_MAX_VERTICAL_SPEED: f32 = 100.0;
 
-// Make the marker component public
-#[derive(Component)]
-pub struct DirigibleBalloon;
+// Buoyancy: lift thins out with altitude, so each load of gas and ballast has a height it settles at
+pub const DIRIGIBLE_GRAVITY: f32 = 30.0;
+pub const DIRIGIBLE_LIFT: f32 = 70.0;  // Lift per unit of gas at sea level
+pub const DIRIGIBLE_LIFT_SCALE_HEIGHT: f32 = 3000.0;  // Lift falls off by a factor of e every this many units up
+pub const DIRIGIBLE_BASE_WEIGHT: f32 = 1.0;  // Pilot and gondola, in the same units as ballast
+pub const DIRIGIBLE_VERTICAL_DRAG: f32 = 0.8;
+pub const BALLAST_DROP_AMOUNT: f32 = 0.1;
+pub const GAS_VENT_RATE: f32 = 0.1;  // Per second while venting
+
+// Landing and tethering
+const LANDING_CLEARANCE: f32 = 3.0;  // Distance to the ground that counts as landed
+const TETHER_BOARD_RANGE: f32 = 15.0;
+const TETHER_SWAY: f32 = 1.5;  // Sway distance per unit of wind speed
+const PROTAGONIST_GRAVITY_SCALE: f32 = 3.0;
 
-// Add this new component for floating animation
 #[derive(Component)]
-pub struct FloatingBalloon {
-    timer: Timer,
+pub struct DirigibleBalloon {
+    pub height: f32,  // How far above its rider or anchor it floats
 }
 
-impl FloatingBalloon {
-    fn new() -> Self {
+#[derive(Component, Clone)]
+pub struct Buoyancy {
+    pub gas: f32,
+    pub ballast: f32,
+    pub landed: bool,
+}
+
+impl Default for Buoyancy {
+    fn default() -> Self {
         Self {
-            timer: Timer::from_seconds(2.0, TimerMode::Once),
+            gas: 1.0,
+            ballast: 1.0,
+            landed: false,
         }
     }
 }
 
+impl Buoyancy {
+    // Net upward acceleration at `altitude`
+    pub fn vertical_acceleration(&self, altitude: f32) -> f32 {
+        let lift = DIRIGIBLE_LIFT * self.gas * (-altitude.max(0.0) / DIRIGIBLE_LIFT_SCALE_HEIGHT).exp();
+        let weight = DIRIGIBLE_GRAVITY * (DIRIGIBLE_BASE_WEIGHT + self.ballast);
+        lift - weight
+    }
+}
+
+// A balloon left on the ground, tied to where its rider got off. The tether refills its
+// gas and ballast, so it always takes off fully loaded.
+#[derive(Component)]
+pub struct TetheredBalloon {
+    pub balloon: Entity,
+}
+
+// Put the protagonist under a new balloon
+pub fn attach_balloon(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<StandardMaterial>>,
+    asset_server: &Res<AssetServer>,
+    protagonist_entity: Entity,
+    protagonist: &mut Protagonist,
+    radius: f32,
+    height: f32,
+) {
+    let balloon = commands.spawn((
+        PbrBundle {
+            mesh: meshes.add(Mesh::from(Sphere::new(radius))),
+            material: materials.add(StandardMaterial {
+                base_color: Color::srgb(1.0, 1.0, 1.0),
+                base_color_texture: Some(asset_server.load("textures/american-flag-background.png")),
+                metallic: 0.8,
+                perceptual_roughness: 0.1,
+                reflectance: 0.7,
+                ..default()
+            }),
+            transform: Transform::from_xyz(0.0, height, 0.0),
+            ..default()
+        },
+        DirigibleBalloon { height },
+    )).id();
+
+    board_balloon(commands, protagonist_entity, protagonist, balloon, height, Buoyancy::default());
+}
+
+fn board_balloon(
+    commands: &mut Commands,
+    protagonist_entity: Entity,
+    protagonist: &mut Protagonist,
+    balloon: Entity,
+    height: f32,
+    buoyancy: Buoyancy,
+) {
+    protagonist.is_dirigible = true;
+    protagonist.is_swimming = false;
+    protagonist.is_falling = false;
+    protagonist.is_climbing = false;
+    protagonist.is_birds_eye = false;
+
+    commands.entity(balloon).insert(Transform::from_xyz(0.0, height, 0.0));
+    commands.entity(protagonist_entity)
+        .add_child(balloon)
+        .insert((Buoyancy { landed: false, ..buoyancy }, GravityScale(0.0)));
+}
+
+// Y attaches a balloon for testing, or detaches it once landed and leaves it tethered
 pub fn toggle_dirigible(
     keyboard_input: Res<ButtonInput<KeyCode>>,
     mut commands: Commands,
-    mut query: Query<(Entity, &mut Protagonist, &Children)>,
+    mut query: Query<(Entity, &mut Protagonist, &Transform, &Children, Option<&Buoyancy>)>,
     balloon_query: Query<&DirigibleBalloon>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
     asset_server: Res<AssetServer>,
+    mut message_display: ResMut<MessageDisplay>,
 ) {
-    if keyboard_input.just_pressed(KeyCode::KeyY) {
-        for (entity, mut protagonist, children) in query.iter_mut() {
-            // Don't allow dirigible mode if driving
-            if protagonist.is_driving {
-                return;
-            }
-            
-            protagonist.is_dirigible = !protagonist.is_dirigible;
-            protagonist.is_swimming = false;
-            protagonist.is_falling = false;
-            protagonist.is_climbing = false;
-            
-            // Only remove existing balloon children with floating animation
-            for &child in children.iter() {
-                if balloon_query.get(child).is_ok() {
-                    let mut entity_commands = commands.entity(child);
-                    if !protagonist.is_dirigible {
-                        // Add floating animation before despawning
-                        entity_commands.insert(FloatingBalloon::new());
-                    } else {
-                        entity_commands.despawn_recursive();
-                    }
-                }
-            }
-            
-            if protagonist.is_dirigible {
-                commands.entity(entity).with_children(|parent| {
-                    parent.spawn((
-                        PbrBundle {
-                            mesh: meshes.add(Mesh::from(Sphere::new(10.0))),
-                            material: materials.add(StandardMaterial {
-                                base_color: Color::srgb(1.0, 1.0, 1.0),
-                                base_color_texture: Some(asset_server.load("textures/american-flag-background.png")),
-                                metallic: 0.8,
-                                perceptual_roughness: 0.1,
-                                reflectance: 0.7,
-                                ..default()
-                            }),
-                            transform: Transform::from_xyz(0.0, 15.0, 0.0),
-                            ..default()
-                        },
-                        DirigibleBalloon,
-                    ));
-                });
-                protagonist.is_birds_eye = false;
+    if !keyboard_input.just_pressed(KeyCode::KeyY) {
+        return;
+    }
+
+    for (entity, mut protagonist, transform, children, buoyancy) in query.iter_mut() {
+        // Don't allow dirigible mode if driving
+        if protagonist.is_driving {
+            return;
+        }
+
+        if !protagonist.is_dirigible {
+            attach_balloon(&mut commands, &mut meshes, &mut materials, &asset_server, entity, &mut protagonist, 10.0, 15.0);
+            continue;
+        }
+
+        let buoyancy = buoyancy.cloned().unwrap_or_default();
+        if !buoyancy.landed {
+            display_message("LAND BEFORE DETACHING THE BALLOON", Color::WHITE, &mut message_display);
+            continue;
+        }
+
+        protagonist.is_dirigible = false;
+        commands.entity(entity)
+            .remove::<Buoyancy>()
+            .insert((GravityScale(PROTAGONIST_GRAVITY_SCALE), Collider::cuboid(1.0, 0.25, 1.0)));
+
+        // Hand the balloon over to a tether at the landing spot
+        for &child in children.iter() {
+            if balloon_query.get(child).is_err() {
+                continue;
             }
+
+            let anchor = commands.spawn((
+                SpatialBundle::from_transform(Transform::from_translation(transform.translation)),
+                TetheredBalloon {
+                    balloon: child,
+                },
+                Interactable::new("board the balloon", TETHER_BOARD_RANGE),
+                Name::new("TetheredBalloon"),
+            )).id();
+            commands.entity(anchor).add_child(child);
         }
+
+        display_message("BALLOON TETHERED", Color::WHITE, &mut message_display);
     }
 }
 
-// Add this new system to handle floating animation
-pub fn animate_floating_balloon(
+pub fn handle_balloon_boarding(
     mut commands: Commands,
-    mut query: Query<(Entity, &mut Transform, &mut FloatingBalloon)>,
+    mut interact_events: EventReader<InteractEvent>,
+    tether_query: Query<&TetheredBalloon>,
+    balloon_query: Query<&DirigibleBalloon>,
+    mut protagonist_query: Query<(Entity, &mut Protagonist)>,
+    mut message_display: ResMut<MessageDisplay>,
+) {
+    for event in interact_events.read() {
+        let Ok(tether) = tether_query.get(event.target) else {
+            continue;
+        };
+        let Ok((protagonist_entity, mut protagonist)) = protagonist_query.get_single_mut() else {
+            continue;
+        };
+        if protagonist.is_dirigible || protagonist.is_driving {
+            continue;
+        }
+
+        let height = balloon_query.get(tether.balloon).map_or(15.0, |balloon| balloon.height);
+        board_balloon(&mut commands, protagonist_entity, &mut protagonist, tether.balloon, height, Buoyancy::default());
+        commands.entity(event.target).despawn();
+
+        display_message("BALLOON REFILLED - SHIFT TO DROP BALLAST, SPACE TO VENT GAS", Color::WHITE, &mut message_display);
+    }
+}
+
+// Tethered balloons bob and lean downwind on their lines
+pub fn animate_tethered_balloons(
+    tether_query: Query<(&GlobalTransform, &TetheredBalloon)>,
+    mut balloon_query: Query<(&mut Transform, &GlobalTransform, &DirigibleBalloon)>,
+    wind: Res<WindField>,
     time: Res<Time>,
+    mut gizmos: Gizmos,
 ) {
-    for (entity, mut transform, mut floating) in query.iter_mut() {
-        floating.timer.tick(time.delta());
-        
-        // Move balloon upward and fade out
-        transform.translation.y += 50.0 * time.delta_seconds();
-        
-        if floating.timer.finished() {
-            commands.entity(entity).despawn_recursive();
+    for (anchor_transform, tether) in tether_query.iter() {
+        let Ok((mut transform, balloon_transform, balloon)) = balloon_query.get_mut(tether.balloon) else {
+            continue;
+        };
+
+        let anchor = anchor_transform.translation();
+        let bob = (time.elapsed_seconds() + anchor.x * 0.01).sin() * 0.5;
+        transform.translation = Vec3::new(0.0, balloon.height + bob, 0.0) + wind.sample(anchor) * TETHER_SWAY;
+
+        gizmos.line(anchor, balloon_transform.translation(), Color::srgb(0.8, 0.8, 0.8));
+    }
+}
+
+// Shift drops ballast to climb, Space vents gas to sink
+pub fn dirigible_ballast_control(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut buoyancy_query: Query<(&Protagonist, &mut Buoyancy)>,
+    mut message_display: ResMut<MessageDisplay>,
+    time: Res<Time>,
+) {
+    let Ok((protagonist, mut buoyancy)) = buoyancy_query.get_single_mut() else {
+        return;
+    };
+    if !protagonist.is_dirigible {
+        return;
+    }
+
+    if keyboard_input.just_pressed(KeyCode::ShiftLeft) {
+        if buoyancy.ballast > 0.0 {
+            buoyancy.ballast = (buoyancy.ballast - BALLAST_DROP_AMOUNT).max(0.0);
+            display_message(format!("BALLAST DROPPED - {:.0}% LEFT", buoyancy.ballast * 100.0), Color::WHITE, &mut message_display);
+        } else {
+            display_message("NO BALLAST LEFT", Color::srgb(1.0, 0.5, 0.0), &mut message_display);
+        }
+    }
+
+    if keyboard_input.pressed(KeyCode::Space) && buoyancy.gas > 0.0 {
+        buoyancy.gas = (buoyancy.gas - GAS_VENT_RATE * time.delta_seconds()).max(0.0);
+        if keyboard_input.just_pressed(KeyCode::Space) {
+            display_message(format!("VENTING GAS - {:.0}% LEFT", buoyancy.gas * 100.0), Color::WHITE, &mut message_display);
         }
     }
 }
 
 pub fn dirigible_control(
     keyboard_input: Res<ButtonInput<KeyCode>>,
-    mut protagonist_query: Query<(&mut Transform, &Protagonist)>,
+    mut protagonist_query: Query<(Entity, &mut Transform, &Protagonist, &mut Buoyancy)>,
     mut velocity_query: Query<&mut LinearVelocity, With<Protagonist>>,
     mut angular_velocity_query: Query<&mut AngularVelocity, With<Protagonist>>,
+    spatial_query: SpatialQuery,
+    wind: Res<WindField>,
+    mut message_display: ResMut<MessageDisplay>,
     time: Res<Time>,
 ) {
-    if let Ok((mut transform, protagonist)) = protagonist_query.get_single_mut() {
+    if let Ok((entity, mut transform, protagonist, mut buoyancy)) = protagonist_query.get_single_mut() {
         if !protagonist.is_dirigible {
             return;
         }
@@ -150,42 +302,55 @@ pub fn dirigible_control(
 
         let mut velocity = velocity_query.single_mut();
         let dt = time.delta_seconds();
-        let mut movement = Vec3::ZERO;
-
-        if keyboard_input.pressed(KeyCode::ShiftLeft) {
-            movement.y += DIRIGIBLE_VERTICAL_SPEED;
-        }
-        if keyboard_input.pressed(KeyCode::Space) {
-            movement.y -= DIRIGIBLE_VERTICAL_SPEED;
-        }
 
+        // Propeller thrust moves us horizontally
+        let mut movement = Vec3::ZERO;
         if keyboard_input.pressed(KeyCode::KeyW) {
             movement += transform.forward() * DIRIGIBLE_FORWARD_SPEED;
         }
         if keyboard_input.pressed(KeyCode::KeyS) {
             movement -= transform.forward() * DIRIGIBLE_FORWARD_SPEED;
         }
+        movement.y = 0.0;
 
-        velocity.0 *= DIRIGIBLE_DAMPENING;
-
-        velocity.0 = if movement != Vec3::ZERO {
-            Vec3::lerp(
-                velocity.0,
-                movement,
-                DIRIGIBLE_ACCELERATION * dt
-            )
+        let mut horizontal = velocity.0.with_y(0.0) * DIRIGIBLE_DAMPENING;
+        horizontal = if movement != Vec3::ZERO {
+            Vec3::lerp(horizontal, movement, DIRIGIBLE_ACCELERATION * dt)
         } else {
-            Vec3::lerp(
-                velocity.0,
-                Vec3::ZERO,
-                DIRIGIBLE_DECELERATION * dt
-            )
+            Vec3::lerp(horizontal, Vec3::ZERO, DIRIGIBLE_DECELERATION * dt)
         };
 
+        // Drift with the wind
+        horizontal += wind.sample(transform.translation) * DIRIGIBLE_WIND_DRIFT;
+
+        // Buoyancy moves us vertically
+        let mut vertical = velocity.0.y + buoyancy.vertical_acceleration(transform.translation.y) * dt;
+        vertical *= 1.0 - (DIRIGIBLE_VERTICAL_DRAG * dt).min(1.0);
+
+        // Settle onto whatever is below instead of bouncing off it
+        let near_ground = spatial_query
+            .cast_ray(
+                transform.translation,
+                Dir3::NEG_Y,
+                LANDING_CLEARANCE,
+                true,
+                SpatialQueryFilter::from_excluded_entities([entity]),
+            )
+            .is_some();
+        let landed = near_ground && vertical <= 0.0;
+        if landed {
+            vertical = 0.0;
+            horizontal = Vec3::ZERO;
+            if !buoyancy.landed {
+                display_message("LANDED - PRESS Y TO DETACH THE BALLOON", Color::WHITE, &mut message_display);
+            }
+        }
+        buoyancy.landed = landed;
+
         velocity.0 = Vec3::new(
-            velocity.0.x.clamp(-DIRIGIBLE_MAX_HORIZONTAL_SPEED, DIRIGIBLE_MAX_HORIZONTAL_SPEED),
-            velocity.0.y.clamp(-DIRIGIBLE_MAX_VERTICAL_SPEED, DIRIGIBLE_MAX_VERTICAL_SPEED),
-            velocity.0.z.clamp(-DIRIGIBLE_MAX_HORIZONTAL_SPEED, DIRIGIBLE_MAX_HORIZONTAL_SPEED)
+            horizontal.x.clamp(-DIRIGIBLE_MAX_HORIZONTAL_SPEED, DIRIGIBLE_MAX_HORIZONTAL_SPEED),
+            vertical.clamp(-DIRIGIBLE_MAX_VERTICAL_SPEED, DIRIGIBLE_MAX_VERTICAL_SPEED),
+            horizontal.z.clamp(-DIRIGIBLE_MAX_HORIZONTAL_SPEED, DIRIGIBLE_MAX_HORIZONTAL_SPEED)
         );
     }
 }
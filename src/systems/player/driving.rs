@@ -1,91 +1,314 @@
 use bevy::prelude::*;
 use avian3d::prelude::*;
-use crate::components::Protagonist;
+use crate::components::{Protagonist, Tank, Health, Fuel};
+use crate::systems::core::interaction::{Interactable, InteractEvent};
+use crate::systems::environments::terrain::{Terrain, TerrainHeightfield};
+use crate::systems::environments::surface::{SurfaceMaterial, surface_at_hit};
+use crate::components::Sentry;
+use crate::systems::core::sentry::{SentryExplosionEvent, SentryStunned, NoiseEvent, stun_sentries};
+use crate::systems::core::screenplay::{MessageDisplay, display_message};
 
-pub fn set_driving_state(
-    protagonist: &mut Protagonist,
-    scene: &mut Handle<Scene>,
-    asset_server: &AssetServer,
-    new_state: bool,
-    commands: &mut Commands,
-    protagonist_entity: Entity,
-    children_query: &Query<&Children>,
-) {
-    protagonist.is_driving = new_state;
-    *scene = if new_state {
-        commands.entity(protagonist_entity)
-            .insert(Collider::cuboid(2.5, 1.5, 3.0))
-            .insert(GravityScale(5.0));
+// The model the protagonist has always driven, sized for the hull collider below. The
+// garages used to show a separate static prop (KB03-apc.glb at scale 6) that was swapped
+// for this one on entry; a parked tank is now the same body that drives off.
+const TANK_SCENE: &str = "models/tank.glb#Scene0";
+const TANK_INTERACT_RANGE: f32 = 20.0;
+const TANK_SEAT_OFFSET: Vec3 = Vec3::new(0.0, 1.0, 0.0);  // Where the driver rides, relative to the tank
+const TANK_EXIT_DISTANCE: f32 = 6.0;  // How far beside the tank the driver climbs out
+const TANK_HEADLIGHT_INTENSITY: f32 = 10000000.0;
+
+// Suspension: one ray per track corner, in tank space, inside the hull collider. The tank
+// drives along its local -X.
+const WHEEL_OFFSETS: [Vec3; 4] = [
+    Vec3::new(-1.1, -0.5, -1.25),
+    Vec3::new(-1.1, -0.5, 1.25),
+    Vec3::new(1.1, -0.5, -1.25),
+    Vec3::new(1.1, -0.5, 1.25),
+];
+const SUSPENSION_REST_LENGTH: f32 = 3.5;
+const SUSPENSION_STIFFNESS: f32 = 20.0;  // Acceleration per unit of compression, per wheel
+const SUSPENSION_DAMPING: f32 = 4.0;
+const TANK_INERTIA: f32 = 0.7;  // Rotational inertia of the hull per unit mass
+
+// Traction
+const LATERAL_GRIP: f32 = 8.0;  // How quickly the tracks stop sideways sliding
+
+// Fuel and hull
+const TANK_MAX_FUEL: f32 = 100.0;
+const TANK_MAX_HULL: f32 = 100.0;
+const FUEL_IDLE_RATE: f32 = 0.2;  // Per second with the engine running
+const FUEL_SPEED_RATE: f32 = 1.5;  // Extra per second at top speed
+const FUEL_FULL_SPEED: f32 = 800.0;
+const EXPLOSION_HULL_DAMAGE: f32 = 25.0;  // At the center of the blast
+const EXPLOSION_DAMAGE_RADIUS: f32 = 30.0;
+
+// EMP
+const EMP_RADIUS: f32 = 150.0;
+const EMP_COOLDOWN: f32 = 20.0;
+const EMP_STUN_DURATION: f32 = 5.0;
+const EMP_NOISE_RADIUS: f32 = 1200.0;  // The discharge is loud enough to draw sentries from far off
+
+#[derive(Component)]
+pub struct TankHeadlight;
+
+#[derive(Component)]
+pub struct TankStatusText;
+
+#[derive(Component)]
+pub struct TankEmp {
+    pub cooldown: Timer,
+}
+
+// What the suspension found under the tank this frame
+#[derive(Component, Default)]
+pub struct TankTraction {
+    pub grounded_wheels: usize,
+    pub grip: f32,  // Average grip of the grounded wheels
+    pub ground_normal: Vec3,
+}
 
-        if let Ok(children) = children_query.get(protagonist_entity) {
-            if let Some(spotlight_entity) = children.first() {
-                commands.entity(*spotlight_entity).insert(SpotLight {
-                    intensity: 10000000.0,
+// Sent to climb out of whichever tank the protagonist is driving
+#[derive(Event)]
+pub struct ExitTankEvent;
+
+pub fn spawn_tank(
+    commands: &mut Commands,
+    asset_server: &Res<AssetServer>,
+    transform: Transform,
+) -> Entity {
+    commands.spawn((
+        SceneBundle {
+            scene: asset_server.load(TANK_SCENE),
+            transform,
+            ..default()
+        },
+        Tank::default(),
+        TankTraction::default(),
+        Health::new(TANK_MAX_HULL),
+        Fuel::new(TANK_MAX_FUEL),
+        TankEmp {
+            cooldown: Timer::from_seconds(EMP_COOLDOWN, TimerMode::Once),
+        },
+        RigidBody::Dynamic,
+        Collider::cuboid(2.5, 1.5, 3.0),
+        GravityScale(5.0),
+        LinearVelocity::default(),
+        AngularVelocity::default(),
+        Interactable::new("enter the tank", TANK_INTERACT_RANGE),
+        Name::new("Tank"),
+    ))
+    .with_children(|parent| {
+        parent.spawn((
+            SpotLightBundle {
+                spot_light: SpotLight {
+                    intensity: 0.0,  // Switched on while someone is driving
                     color: Color::srgb(1.0, 0.2, 0.2),
                     outer_angle: 0.6,
                     inner_angle: 0.3,
                     shadows_enabled: false,
                     range: 100.0,
                     ..default()
-                });
-                commands.entity(*spotlight_entity).insert(Transform::from_xyz(0.0, 20.0, 0.0)
-                    .looking_at(Vec3::ZERO, Vec3::Z));
-            }
+                },
+                transform: Transform::from_xyz(0.0, 20.0, 0.0)
+                    .looking_at(Vec3::ZERO, Vec3::Z),
+                ..default()
+            },
+            TankHeadlight,
+        ));
+    })
+    .id()
+}
+
+// Climb into a tank: the protagonist is hidden and carried in the seat until they exit
+pub fn handle_tank_entry(
+    mut commands: Commands,
+    mut interact_events: EventReader<InteractEvent>,
+    mut tank_query: Query<&mut Tank>,
+    mut protagonist_query: Query<(Entity, &mut Protagonist, &mut LinearVelocity)>,
+) {
+    for event in interact_events.read() {
+        let Ok(mut tank) = tank_query.get_mut(event.target) else {
+            continue;
+        };
+        let Ok((protagonist_entity, mut protagonist, mut velocity)) = protagonist_query.get_single_mut() else {
+            continue;
+        };
+        if protagonist.is_driving || tank.driver.is_some() {
+            continue;
         }
-        asset_server.load("models/tank.glb#Scene0")
-    } else {
-        // Reset to original protagonist collider
+
+        protagonist.is_driving = true;
+        protagonist.is_outside = true;
+        velocity.0 = Vec3::ZERO;
+        tank.driver = Some(protagonist_entity);
+        tank.unattended = false;
+
         commands.entity(protagonist_entity)
-            .insert(Collider::cuboid(1.0, 0.25, 1.0))
-            .insert(GravityScale(3.0));
-
-        if let Ok(children) = children_query.get(protagonist_entity) {
-            if let Some(spotlight_entity) = children.first() {
-                commands.entity(*spotlight_entity).insert(SpotLight {
-                    intensity: 5000000.0,
-                    color: Color::srgb(1.0, 0.95, 0.9),
-                    outer_angle: 0.5,
-                    inner_angle: 0.2,
-                    shadows_enabled: true,
-                    range: 50.0,
-                    ..default()
-                });
-                commands.entity(*spotlight_entity).insert(Transform::from_xyz(0.0, 15.0, 2.0)
-                    .looking_at(Vec3::new(0.0, 0.0, -10.0), Vec3::Y));
-            }
-        }
-        asset_server.load("models/Protagonist.glb#Scene0")
+            .insert((RigidBody::Kinematic, Visibility::Hidden))
+            .remove::<Collider>();
+        commands.entity(event.target).remove::<Interactable>();
+    }
+}
+
+// Climb out beside the tank and leave it parked where it is
+pub fn handle_tank_exit(
+    mut commands: Commands,
+    mut exit_events: EventReader<ExitTankEvent>,
+    mut tank_query: Query<(Entity, &mut Tank, &Transform, &mut LinearVelocity, &mut AngularVelocity), Without<Protagonist>>,
+    mut protagonist_query: Query<(Entity, &mut Protagonist, &mut Transform)>,
+    heightfield: Res<TerrainHeightfield>,
+) {
+    if exit_events.read().count() == 0 {
+        return;
+    }
+
+    let Ok((protagonist_entity, mut protagonist, mut protagonist_transform)) = protagonist_query.get_single_mut() else {
+        return;
     };
+
+    for (tank_entity, mut tank, tank_transform, mut velocity, mut angular_velocity) in tank_query.iter_mut() {
+        if tank.driver != Some(protagonist_entity) {
+            continue;
+        }
+
+        tank.driver = None;
+        tank.unattended = true;
+        velocity.0 = Vec3::ZERO;
+        angular_velocity.0 = Vec3::ZERO;
+        commands.entity(tank_entity).insert(Interactable::new("enter the tank", TANK_INTERACT_RANGE));
+
+        protagonist.is_driving = false;
+        let mut exit_position = tank_transform.translation
+            + tank_transform.forward().as_vec3() * TANK_EXIT_DISTANCE
+            + TANK_SEAT_OFFSET;
+
+        // Parked on a slope the exit can end up inside the hill; step out on top of it
+        // instead. Anything deeper is an interior below the terrain, so leave it alone.
+        let ground = heightfield.height_at(exit_position.x, exit_position.z) + TANK_SEAT_OFFSET.y;
+        if (0.0..TANK_EXIT_DISTANCE).contains(&(ground - exit_position.y)) {
+            exit_position.y = ground;
+        }
+        protagonist_transform.translation = exit_position;
+        commands.entity(protagonist_entity)
+            .insert((
+                RigidBody::Dynamic,
+                Collider::cuboid(1.0, 0.25, 1.0),
+                Visibility::Inherited,
+                LinearVelocity::default(),
+            ));
+    }
 }
 
 pub fn toggle_driving(
     keyboard_input: Res<ButtonInput<KeyCode>>,
-    mut query: Query<(Entity, &Transform, &mut Protagonist, &mut Handle<Scene>)>,
-    children_query: Query<&Children>,
-    asset_server: Res<AssetServer>,
-    mut commands: Commands,
+    protagonist_query: Query<&Protagonist>,
+    mut exit_events: EventWriter<ExitTankEvent>,
 ) {
     if keyboard_input.just_pressed(KeyCode::KeyT) {
-        for (entity, transform, mut protagonist, mut scene) in query.iter_mut() {
-            let new_state = !protagonist.is_driving;
-            set_driving_state(
-                &mut protagonist,
-                &mut scene,
-                &asset_server,
-                new_state,
-                &mut commands,
-                entity,
-                &children_query,
-            );
+        if protagonist_query.get_single().map_or(false, |protagonist| protagonist.is_driving) {
+            exit_events.send(ExitTankEvent);
+        }
+    }
+}
+
+// Carry the driver along in the seat so the camera and sentries follow the tank
+pub fn seat_driver(
+    tank_query: Query<(&Tank, &Transform), Without<Protagonist>>,
+    mut protagonist_query: Query<(&mut Transform, &mut LinearVelocity), With<Protagonist>>,
+) {
+    for (tank, tank_transform) in tank_query.iter() {
+        let Some(driver) = tank.driver else {
+            continue;
+        };
+        if let Ok((mut transform, mut velocity)) = protagonist_query.get_mut(driver) {
+            // Only the heading, so the camera doesn't roll with the hull
+            let (yaw, _, _) = tank_transform.rotation.to_euler(EulerRot::YXZ);
+            transform.translation = tank_transform.translation + TANK_SEAT_OFFSET;
+            transform.rotation = Quat::from_rotation_y(yaw);
+            velocity.0 = Vec3::ZERO;
+        }
+    }
+}
+
+pub fn update_tank_headlights(
+    tank_query: Query<&Tank>,
+    mut headlight_query: Query<(&Parent, &mut SpotLight), With<TankHeadlight>>,
+) {
+    for (parent, mut light) in headlight_query.iter_mut() {
+        let driven = tank_query.get(parent.get()).map_or(false, |tank| tank.driver.is_some());
+        light.intensity = if driven { TANK_HEADLIGHT_INTENSITY } else { 0.0 };
+    }
+}
+
+// Out of fuel or with the hull breached the tank won't drive
+pub fn is_tank_disabled(hull: &Health, fuel: &Fuel) -> bool {
+    hull.current <= 0.0 || fuel.current <= 0.0
+}
+
+// Spring each track corner off the ground and let the tracks resist sliding sideways.
+// Runs for parked tanks too so they settle on their suspension.
+pub fn tank_suspension(
+    mut tank_query: Query<(Entity, &Transform, &mut LinearVelocity, &mut AngularVelocity, &mut TankTraction)>,
+    surface_query: Query<(Option<&SurfaceMaterial>, Option<&Terrain>)>,
+    heightfield: Res<TerrainHeightfield>,
+    spatial_query: SpatialQuery,
+    time: Res<Time>,
+) {
+    let delta = time.delta_seconds();
+
+    for (entity, transform, mut velocity, mut angular_velocity, mut traction) in tank_query.iter_mut() {
+        let up = transform.up().as_vec3();
+        let mut linear_acceleration = Vec3::ZERO;
+        let mut angular_acceleration = Vec3::ZERO;
+        let mut grounded_wheels = 0;
+        let mut total_grip = 0.0;
+        let mut ground_normal = Vec3::ZERO;
+
+        for offset in WHEEL_OFFSETS {
+            let arm = transform.rotation * offset;
+            let Some(hit) = spatial_query.cast_ray(
+                transform.translation + arm,
+                transform.down(),
+                SUSPENSION_REST_LENGTH,
+                true,
+                SpatialQueryFilter::from_excluded_entities([entity]),
+            ) else {
+                continue;
+            };
+
+            // Push up in proportion to compression, damped by how fast the wheel is closing on the ground
+            let compression = SUSPENSION_REST_LENGTH - hit.time_of_impact;
+            let closing_speed = -(velocity.0 + angular_velocity.0.cross(arm)).dot(up);
+            let spring = (SUSPENSION_STIFFNESS * compression + SUSPENSION_DAMPING * closing_speed).max(0.0);
+            linear_acceleration += up * spring;
+            angular_acceleration += arm.cross(up * spring) / TANK_INERTIA;
+
+            let point = transform.translation + arm + transform.down().as_vec3() * hit.time_of_impact;
+            let surface = surface_query.get(hit.entity).unwrap_or((None, None));
+            grounded_wheels += 1;
+            total_grip += surface_at_hit(surface, point, &heightfield).traction();
+            ground_normal += hit.normal;
+        }
+
+        velocity.0 += linear_acceleration * delta;
+        angular_velocity.0 += angular_acceleration * delta;
+
+        traction.grounded_wheels = grounded_wheels;
+        traction.grip = if grounded_wheels > 0 { total_grip / grounded_wheels as f32 } else { 0.0 };
+        traction.ground_normal = ground_normal.normalize_or_zero();
+
+        if grounded_wheels > 0 {
+            let contact = grounded_wheels as f32 / WHEEL_OFFSETS.len() as f32;
+            let lateral_dir = transform.forward().as_vec3();
+            let lateral_speed = velocity.0.dot(lateral_dir);
+            velocity.0 -= lateral_dir * lateral_speed * (LATERAL_GRIP * traction.grip * contact * delta).min(1.0);
         }
     }
 }
 
 pub fn driving_control(
     keyboard_input: Res<ButtonInput<KeyCode>>,
-    mut protagonist_query: Query<(Entity, &mut Transform, &Protagonist)>,
-    mut velocity_query: Query<&mut LinearVelocity, With<Protagonist>>,
-    mut angular_velocity_query: Query<&mut AngularVelocity, With<Protagonist>>,
+    mut tank_query: Query<(&Tank, &Transform, &mut LinearVelocity, &mut AngularVelocity, &TankTraction, &Health, &Fuel)>,
     time: Res<Time>,
 ) {
     // Base driving parameters
@@ -94,31 +317,29 @@ pub fn driving_control(
     const BASE_MIN_SPEED: f32 = -400.0;
     const BASE_ACCELERATION: f32 = 200.0;
     const BRAKE_FORCE: f32 = 600.0;
-    const DRIFT_FACTOR: f32 = 0.92;
     const TURN_SENSITIVITY: f32 = 2.5;
     const SPEED_TURN_FACTOR: f32 = 0.4;
-    const FIXED_HEIGHT: f32 = 4.0;  // Fixed height for the tank
+    const PIVOT_TURN_RATIO: f32 = 0.3;  // Tracks can still turn on the spot
+    const MAX_CLIMB_GRADE: f32 = 0.6;  // Slope (rise over distance) the tank can't climb past
 
-    if let Ok((protagonist_entity, mut protagonist_transform, protagonist)) = protagonist_query.get_single_mut() {
-        if !protagonist.is_driving {
-            return;
+    for (tank, tank_transform, mut current_velocity, mut angular_velocity, traction, hull, fuel) in tank_query.iter_mut() {
+        // No input without a driver, and none with the tracks off the ground (airborne or tipped over)
+        if tank.driver.is_none() || traction.grounded_wheels == 0 || is_tank_disabled(hull, fuel) {
+            continue;
         }
 
-        // Keep tank at fixed height
-        protagonist_transform.translation.y = FIXED_HEIGHT;
-        
-        let mut current_velocity = velocity_query.single_mut();
-        current_velocity.0.y = 0.0;  // No vertical movement
-
-        // Extract only Y rotation and force upright orientation
-        let (yaw, _, _) = protagonist_transform.rotation.to_euler(EulerRot::YXZ);
-        protagonist_transform.rotation = Quat::from_rotation_y(yaw);
+        let contact = traction.grounded_wheels as f32 / WHEEL_OFFSETS.len() as f32;
+        let traction_factor = traction.grip * contact;
 
-        // Decompose velocity into forward and lateral components
-        let forward_dir = protagonist_transform.left().as_vec3();
-        let right_dir = protagonist_transform.forward().as_vec3();
+        // Drive along the ground rather than into it
+        let forward_dir = tank_transform.left().as_vec3()
+            .reject_from_normalized(traction.ground_normal)
+            .normalize_or_zero();
         let current_speed = current_velocity.0.dot(forward_dir);
-        let lateral_speed = current_velocity.0.dot(right_dir);
+
+        // Steeper climbs lower the top speed
+        let uphill = (forward_dir.y * current_speed.signum()).max(0.0);
+        let slope_factor = (1.0 - uphill / MAX_CLIMB_GRADE).clamp(0.0, 1.0);
 
         let mut acceleration_amount = 0.0;
         if keyboard_input.pressed(KeyCode::KeyW) {
@@ -133,8 +354,9 @@ pub fn driving_control(
             acceleration_amount = -current_speed.signum() * BRAKE_FORCE * 0.5 * time.delta_seconds();
         }
 
-        let new_speed = (current_speed + acceleration_amount)
-            .clamp(BASE_MIN_SPEED, BASE_MAX_SPEED);
+        let new_speed = (current_speed + acceleration_amount * traction_factor)
+            .clamp(BASE_MIN_SPEED * slope_factor, BASE_MAX_SPEED * slope_factor);
+        current_velocity.0 += forward_dir * (new_speed - current_speed);
 
         // Calculate turning based on speed
         let turn_amount = if keyboard_input.pressed(KeyCode::KeyA) {
@@ -145,24 +367,138 @@ pub fn driving_control(
             0.0
         };
 
-        // Adjust turn rate based on speed
-        let speed_ratio = (current_speed.abs() / BASE_MAX_SPEED).powf(SPEED_TURN_FACTOR);
-        let adjusted_turn = turn_amount * TURN_SENSITIVITY * speed_ratio;
+        let speed_ratio = (current_speed.abs() / BASE_MAX_SPEED).powf(SPEED_TURN_FACTOR).max(PIVOT_TURN_RATIO);
+        let target_turn = turn_amount * TURN_SENSITIVITY * speed_ratio;
+
+        // Turn about the hull's own up axis; slippery ground makes the tracks slow to respond
+        let up = tank_transform.up().as_vec3();
+        let current_turn = angular_velocity.0.dot(up);
+        angular_velocity.0 += up * (target_turn - current_turn) * traction_factor.min(1.0);
+    }
+}
+
+// Burn fuel while someone is driving, faster at speed
+pub fn consume_tank_fuel(
+    mut tank_query: Query<(&Tank, &LinearVelocity, &mut Fuel)>,
+    mut message_display: ResMut<MessageDisplay>,
+    time: Res<Time>,
+) {
+    for (tank, velocity, mut fuel) in tank_query.iter_mut() {
+        if tank.driver.is_none() || fuel.current <= 0.0 {
+            continue;
+        }
 
-        // Apply drift mechanics
-        let new_lateral = lateral_speed * DRIFT_FACTOR;
+        let speed_fraction = (velocity.0.length() / FUEL_FULL_SPEED).min(1.0);
+        fuel.current -= (FUEL_IDLE_RATE + FUEL_SPEED_RATE * speed_fraction) * time.delta_seconds();
+        if fuel.current <= 0.0 {
+            fuel.current = 0.0;
+            display_message("OUT OF FUEL", Color::srgb(1.0, 0.5, 0.0), &mut message_display);
+        }
+    }
+}
 
-        // Combine forward and lateral velocities
-        current_velocity.0 = forward_dir * new_speed + right_dir * new_lateral;
+// Sentry blasts dent the hull of any tank close by, driven or parked
+pub fn damage_tanks_from_explosions(
+    mut explosion_events: EventReader<SentryExplosionEvent>,
+    mut tank_query: Query<(&Transform, &mut Health), With<Tank>>,
+    mut message_display: ResMut<MessageDisplay>,
+) {
+    for explosion in explosion_events.read() {
+        for (transform, mut hull) in tank_query.iter_mut() {
+            let distance = transform.translation.distance(explosion.position);
+            if distance > EXPLOSION_DAMAGE_RADIUS || hull.current <= 0.0 {
+                continue;
+            }
 
-        // Apply rotation through angular velocity
-        for mut angular_velocity in angular_velocity_query.iter_mut() {
-            angular_velocity.0 = Vec3::new(0.0, adjusted_turn, 0.0);
+            hull.current -= EXPLOSION_HULL_DAMAGE * (1.0 - distance / EXPLOSION_DAMAGE_RADIUS);
+            if hull.current <= 0.0 {
+                hull.current = 0.0;
+                display_message("HULL BREACHED - TANK DISABLED", Color::srgb(1.0, 0.0, 0.0), &mut message_display);
+            }
         }
+    }
+}
+
+// F fires an EMP from the driven tank, knocking out nearby sentries at the cost of a loud bang
+pub fn fire_tank_emp(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut tank_query: Query<(&Tank, &Transform, &mut TankEmp, &Health)>,
+    sentry_query: Query<(Entity, &Transform), (With<Sentry>, Without<SentryStunned>)>,
+    mut noise_events: EventWriter<NoiseEvent>,
+    mut message_display: ResMut<MessageDisplay>,
+    time: Res<Time>,
+) {
+    for (tank, transform, mut emp, hull) in tank_query.iter_mut() {
+        emp.cooldown.tick(time.delta());
 
-        // Optional: Add drift particles or effects when drifting
-        if new_lateral.abs() > 50.0 {
-            info!("Drifting! Speed: {}", new_lateral);
+        if tank.driver.is_none() || hull.current <= 0.0 || !keyboard_input.just_pressed(KeyCode::KeyF) {
+            continue;
+        }
+        if !emp.cooldown.finished() {
+            display_message(
+                format!("EMP RECHARGING ({:.0}s)", emp.cooldown.remaining_secs().ceil()),
+                Color::srgb(0.3, 0.6, 1.0),
+                &mut message_display,
+            );
+            continue;
         }
+
+        emp.cooldown.reset();
+        let stunned = stun_sentries(&mut commands, &sentry_query, transform.translation, EMP_RADIUS, EMP_STUN_DURATION);
+        noise_events.send(NoiseEvent {
+            position: transform.translation,
+            radius: EMP_NOISE_RADIUS,
+        });
+        display_message(format!("EMP DISCHARGED - {} SENTRIES DOWN", stunned), Color::srgb(0.3, 0.6, 1.0), &mut message_display);
+    }
+}
+
+pub fn setup_tank_hud(mut commands: Commands) {
+    commands.spawn((
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font_size: 22.0,
+                color: Color::WHITE,
+                ..default()
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(50.0),
+            right: Val::Px(20.0),
+            ..default()
+        }),
+        TankStatusText,
+    ));
+}
+
+pub fn update_tank_hud(
+    tank_query: Query<(&Tank, &Health, &Fuel, &TankEmp)>,
+    mut text_query: Query<&mut Text, With<TankStatusText>>,
+) {
+    let Ok(mut text) = text_query.get_single_mut() else {
+        return;
+    };
+
+    let status = tank_query
+        .iter()
+        .find(|(tank, _, _, _)| tank.driver.is_some())
+        .map(|(_, hull, fuel, emp)| {
+            let warning = if is_tank_disabled(hull, fuel) { "  DISABLED" } else { "" };
+            let emp_status = if emp.cooldown.finished() { "READY" } else { "CHARGING" };
+            format!(
+                "HULL {:.0}%  FUEL {:.0}%  EMP {}{}",
+                hull.fraction() * 100.0,
+                fuel.fraction() * 100.0,
+                emp_status,
+                warning,
+            )
+        })
+        .unwrap_or_default();
+
+    if text.sections[0].value != status {
+        text.sections[0].value = status;
     }
 }
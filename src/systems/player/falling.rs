@@ -1,27 +1,89 @@
 use bevy::prelude::*;
 use bevy::animation::RepeatAnimation;
 use avian3d::prelude::*;
-use crate::components::Protagonist;
+use crate::components::{Protagonist, Health};
 use crate::resources::{ProtagonistAnimations, PROTAGONIST_ANIMATIONS};
+use crate::systems::core::screenplay::{MessageDisplay, display_message};
+use crate::systems::core::sentry::NoiseEvent;
+use crate::systems::core::setup::PROTAGONIST_START;
+use crate::systems::environments::lighting::{LightingStack, LightingZone};
 use std::time::Duration;
 
+// Landing thresholds (downward speed at impact)
+pub const MEDIUM_LANDING_SPEED: f32 = 25.0;
+pub const HEAVY_LANDING_SPEED: f32 = 45.0;
+pub const LETHAL_LANDING_SPEED: f32 = 80.0;
+
+// Avian's default gravity scaled by the protagonist's GravityScale(3.0)
+pub const FALL_GRAVITY: f32 = 9.81 * 3.0;
+
+// Landing consequences
+const LANDING_RECOVERY_SECONDS: f32 = 0.6;
+const HEAVY_LANDING_RECOVERY_SECONDS: f32 = 1.5;
+const LANDING_RECOVERY_SPEED_MULTIPLIER: f32 = 0.3;
+const HEAVY_LANDING_NOISE_RADIUS: f32 = 400.0;
+
+#[derive(Event)]
+pub struct LandingEvent {
+    pub impact_speed: f32,
+}
+
+// Tracks how hard the protagonist is falling and the slowdown after a landing
+#[derive(Component)]
+pub struct FallTracker {
+    pub peak_fall_speed: f32,
+    pub last_impact_speed: f32,
+    pub recovery: Timer,
+}
+
+impl Default for FallTracker {
+    fn default() -> Self {
+        let mut recovery = Timer::from_seconds(LANDING_RECOVERY_SECONDS, TimerMode::Once);
+        recovery.tick(Duration::from_secs_f32(LANDING_RECOVERY_SECONDS));
+        Self {
+            peak_fall_speed: 0.0,
+            last_impact_speed: 0.0,
+            recovery,
+        }
+    }
+}
+
+impl FallTracker {
+    // Movement speed multiplier while recovering from a landing
+    pub fn speed_multiplier(&self) -> f32 {
+        if self.recovery.finished() {
+            1.0
+        } else {
+            LANDING_RECOVERY_SPEED_MULTIPLIER
+        }
+    }
+}
+
+// Speed the protagonist would hit the ground at after falling `height` units
+pub fn impact_speed_for_drop(height: f32) -> f32 {
+    (2.0 * FALL_GRAVITY * height.max(0.0)).sqrt()
+}
+
 pub fn check_falling(
     mut commands: Commands,
-    mut protagonist_query: Query<(Entity, &mut Protagonist, &Transform, &LinearVelocity, &Children)>,
+    mut protagonist_query: Query<(Entity, &mut Protagonist, &Transform, &LinearVelocity, &Children, &mut FallTracker)>,
     mut spotlight_query: Query<&mut SpotLight>,
     spatial_query: SpatialQuery,
     mut gizmos: Gizmos,
-    mut ambient_light: ResMut<AmbientLight>,
+    mut lighting: ResMut<LightingStack>,
+    mut landing_events: EventWriter<LandingEvent>,
 ) {
-    for (entity, mut protagonist, transform, _velocity, children) in protagonist_query.iter_mut() {
+    for (entity, mut protagonist, transform, velocity, children, mut fall_tracker) in protagonist_query.iter_mut() {
         // Don't check falling for vehicles at all
         if protagonist.is_driving {
+            fall_tracker.peak_fall_speed = 0.0;
             continue;  // Skip the entire check for vehicles
         }
 
         // Don't set falling state if swimming or climbing
         if protagonist.is_swimming || protagonist.is_climbing {
             protagonist.is_falling = false;
+            fall_tracker.peak_fall_speed = 0.0;
             continue;
         }
 
@@ -45,17 +107,17 @@ pub fn check_falling(
             overhead_filter
         );
 
-        // Update is_outside status and adjust ambient light based on overhead hits
+        // Update is_outside status and dim the lighting based on overhead hits
         protagonist.is_outside = overhead_hits.is_empty();
         if !protagonist.is_outside {
-            ambient_light.brightness = 200.0; // Reduced brightness when under cover
+            lighting.push(LightingZone::Indoors, 1.0); // Reduced brightness when under cover
             for child in children {
                 if let Ok(mut spotlight) = spotlight_query.get_mut(*child) {
                     spotlight.intensity = 0.0;
                 }
             }
         } else {
-            ambient_light.brightness = 400.0; // Normal brightness in open areas
+            lighting.pop(LightingZone::Indoors); // Normal brightness in open areas
             // Turn on spotlight when outside
             for child in children {
                 if let Ok(mut spotlight) = spotlight_query.get_mut(*child) {
@@ -81,10 +143,24 @@ pub fn check_falling(
 
         let is_grounded = !hits.is_empty();
         
+        // Track the fastest downward speed reached in the air (the balloon lowers us gently)
+        if !is_grounded && !protagonist.is_dirigible {
+            fall_tracker.peak_fall_speed = fall_tracker.peak_fall_speed.max(-velocity.0.y);
+        } else if protagonist.is_dirigible {
+            fall_tracker.peak_fall_speed = 0.0;
+        }
+
         // Reset jumping state when landing
         if is_grounded && (protagonist.is_jumping || protagonist.is_falling) {
             protagonist.is_jumping = false;
             protagonist.is_falling = false;
+
+            // Record the impact
+            fall_tracker.last_impact_speed = fall_tracker.peak_fall_speed;
+            fall_tracker.peak_fall_speed = 0.0;
+            landing_events.send(LandingEvent {
+                impact_speed: fall_tracker.last_impact_speed,
+            });
         } else if !is_grounded && !protagonist.is_jumping {
             protagonist.is_falling = true;
         }
@@ -134,3 +210,74 @@ pub fn handle_falling_animation(
         }
     }
 }
+
+// Apply the consequences of a landing: stumble, damage, noise and death
+pub fn handle_landing(
+    mut landing_events: EventReader<LandingEvent>,
+    mut protagonist_query: Query<(&mut Transform, &mut LinearVelocity, &mut Health, &mut FallTracker), With<Protagonist>>,
+    mut animation_players: Query<(&mut AnimationPlayer, &mut AnimationTransitions)>,
+    animations: Res<ProtagonistAnimations>,
+    mut noise_events: EventWriter<NoiseEvent>,
+    mut message_display: ResMut<MessageDisplay>,
+    time: Res<Time>,
+) {
+    let Ok((mut transform, mut velocity, mut health, mut fall_tracker)) = protagonist_query.get_single_mut() else {
+        return;
+    };
+
+    fall_tracker.recovery.tick(time.delta());
+
+    for landing in landing_events.read() {
+        if landing.impact_speed < MEDIUM_LANDING_SPEED {
+            continue;
+        }
+
+        info!("Hard landing at {:.1} units/s", landing.impact_speed);
+
+        // Stumble and slow down briefly
+        let recovery_seconds = if landing.impact_speed >= HEAVY_LANDING_SPEED {
+            HEAVY_LANDING_RECOVERY_SECONDS
+        } else {
+            LANDING_RECOVERY_SECONDS
+        };
+        fall_tracker.recovery = Timer::from_seconds(recovery_seconds, TimerMode::Once);
+
+        for (mut player, mut transitions) in animation_players.iter_mut() {
+            if let Some(land) = PROTAGONIST_ANIMATIONS.get("JUMP_LAND") {
+                transitions
+                    .play(
+                        &mut player,
+                        animations.animations[*land],
+                        Duration::from_millis(100),
+                    )
+                    .set_repeat(RepeatAnimation::Never);
+            }
+        }
+
+        if landing.impact_speed < HEAVY_LANDING_SPEED {
+            continue;
+        }
+
+        // Heavy landings hurt in proportion to how close they were to lethal, and are loud
+        let severity = (landing.impact_speed - HEAVY_LANDING_SPEED) / (LETHAL_LANDING_SPEED - HEAVY_LANDING_SPEED);
+        health.current -= severity.clamp(0.0, 1.0) * health.max;
+
+        noise_events.send(NoiseEvent {
+            position: transform.translation,
+            radius: HEAVY_LANDING_NOISE_RADIUS,
+        });
+
+        if health.current <= 0.0 {
+            display_message("YOU FELL TO YOUR DEATH", Color::srgb(1.0, 0.0, 0.0), &mut message_display);
+            transform.translation = PROTAGONIST_START.position;
+            velocity.0 = Vec3::ZERO;
+            health.current = health.max;
+        } else {
+            display_message(
+                format!("HARD LANDING - HEALTH {:.0}%", health.fraction() * 100.0),
+                Color::srgb(1.0, 0.5, 0.0),
+                &mut message_display,
+            );
+        }
+    }
+}
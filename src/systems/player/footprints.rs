@@ -0,0 +1,312 @@
+use bevy::prelude::*;
+use std::collections::HashMap;
+use crate::components::{Protagonist, Sentry, Tank};
+use crate::systems::core::screenplay::{MessageDisplay, display_message};
+use crate::systems::core::sentry::SentryStunned;
+use crate::systems::environments::surface::GroundSurface;
+use crate::systems::environments::terrain::TerrainHeightfield;
+use crate::systems::environments::tram::TramRider;
+use crate::systems::environments::weather::Weather;
+use crate::systems::environments::wind::WindField;
+use crate::systems::player::driving::TankTraction;
+
+// Trails
+const FOOTPRINT_STRIDE: f32 = 3.0;  // Distance between boot prints
+const FOOTPRINT_CRAWL_STRIDE: f32 = 2.0;
+const FOOTPRINT_TRACK_SPACING: f32 = 2.5;  // Distance between tank tread marks
+const FOOTPRINT_FOOT_OFFSET: f32 = 0.4;  // Left and right of the center line
+const FOOTPRINT_TRACK_OFFSET: f32 = 2.0;  // Half the distance between the tank's treads
+const FOOTPRINT_LIFT: f32 = 0.05;  // Above the ground so they don't flicker
+const TANK_TRACK_MAX_CLEARANCE: f32 = 5.0;  // Only mark terrain this close under the tank
+
+// Fading
+const FOOTPRINT_LIFETIME: f32 = 60.0;  // Seconds in still air
+const CRAWL_MARK_LIFETIME: f32 = 40.0;  // Shallower than boot prints
+const TANK_TREAD_LIFETIME: f32 = 120.0;  // Pressed in deep
+const FOOTPRINT_WIND_EROSION: f32 = 0.1;  // Extra ageing per unit of wind speed
+const FOOTPRINT_FADE_STEPS: usize = 4;
+const FOOTPRINT_MAX_COUNT: usize = 500;  // Oldest prints go first past this
+
+// Tracking
+const FOOTPRINT_TRACK_RADIUS: f32 = 40.0;  // How far a searching sentry spots prints
+const FOOTPRINT_MIN_TRACKABLE: f32 = 0.3;  // Fainter prints than this can't be followed
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum FootprintKind {
+    Boot,
+    Crawl,
+    TankTread,
+}
+
+impl FootprintKind {
+    // Width and length of one mark
+    fn size(self) -> Vec2 {
+        match self {
+            FootprintKind::Boot => Vec2::new(0.5, 1.0),
+            FootprintKind::Crawl => Vec2::new(1.4, 1.8),
+            FootprintKind::TankTread => Vec2::new(1.2, 2.5),
+        }
+    }
+
+    fn lifetime(self) -> f32 {
+        match self {
+            FootprintKind::Boot => FOOTPRINT_LIFETIME,
+            FootprintKind::Crawl => CRAWL_MARK_LIFETIME,
+            FootprintKind::TankTread => TANK_TREAD_LIFETIME,
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct Footprint {
+    pub kind: FootprintKind,
+    pub left_at: f32,  // Elapsed seconds when it was made
+    pub age: f32,  // Wind ages prints faster than real time
+    pub strength: f32,  // How clear it was to begin with, from the surface
+}
+
+impl Footprint {
+    // How clear it is now, 0 once it has filled in
+    pub fn freshness(&self) -> f32 {
+        self.strength * (1.0 - self.age / self.kind.lifetime()).max(0.0)
+    }
+}
+
+#[derive(Resource)]
+pub struct FootprintAssets {
+    meshes: HashMap<FootprintKind, Handle<Mesh>>,
+    fade_materials: Vec<Handle<StandardMaterial>>,  // Faintest first
+}
+
+impl FootprintAssets {
+    fn mesh(&self, kind: FootprintKind) -> Handle<Mesh> {
+        self.meshes[&kind].clone()
+    }
+
+    fn material(&self, freshness: f32) -> Handle<StandardMaterial> {
+        let step = ((freshness * FOOTPRINT_FADE_STEPS as f32).ceil() as usize).clamp(1, FOOTPRINT_FADE_STEPS);
+        self.fade_materials[step - 1].clone()
+    }
+}
+
+pub fn setup_footprints(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let footprint_meshes = [FootprintKind::Boot, FootprintKind::Crawl, FootprintKind::TankTread]
+        .into_iter()
+        .map(|kind| {
+            let size = kind.size();
+            (kind, meshes.add(Cuboid::new(size.x, 0.02, size.y)))
+        })
+        .collect();
+
+    let fade_materials = (1..=FOOTPRINT_FADE_STEPS)
+        .map(|step| {
+            let alpha = step as f32 / FOOTPRINT_FADE_STEPS as f32 * 0.7;
+            materials.add(StandardMaterial {
+                base_color: Color::srgba(0.25, 0.3, 0.4, alpha),
+                alpha_mode: AlphaMode::Blend,
+                perceptual_roughness: 1.0,
+                unlit: true,
+                ..default()
+            })
+        })
+        .collect();
+
+    commands.insert_resource(FootprintAssets {
+        meshes: footprint_meshes,
+        fade_materials,
+    });
+}
+
+fn spawn_footprint(
+    commands: &mut Commands,
+    assets: &FootprintAssets,
+    kind: FootprintKind,
+    position: Vec3,
+    heading: Vec3,
+    strength: f32,
+    now: f32,
+) {
+    commands.spawn((
+        PbrBundle {
+            mesh: assets.mesh(kind),
+            material: assets.material(strength),
+            transform: Transform::from_translation(position + Vec3::Y * FOOTPRINT_LIFT)
+                .looking_to(heading, Vec3::Y),
+            ..default()
+        },
+        Footprint {
+            kind,
+            left_at: now,
+            age: 0.0,
+            strength,
+        },
+    ));
+}
+
+// Leave prints behind the protagonist on soft ground, alternating feet
+pub fn leave_footprints(
+    mut commands: Commands,
+    assets: Res<FootprintAssets>,
+    protagonist_query: Query<(&Protagonist, &GroundSurface), Without<TramRider>>,
+    mut last_print: Local<Option<Vec3>>,
+    mut left_foot: Local<bool>,
+    time: Res<Time>,
+) {
+    let Ok((protagonist, ground)) = protagonist_query.get_single() else {
+        return;
+    };
+
+    let strength = ground.material.footprint_visibility();
+    let on_foot = !protagonist.is_falling
+        && !protagonist.is_swimming
+        && !protagonist.is_climbing
+        && !protagonist.is_driving
+        && !protagonist.is_dirigible;
+    let Some(contact) = ground.contact.filter(|_| on_foot && strength > 0.0) else {
+        *last_print = None;
+        return;
+    };
+
+    let Some(previous) = *last_print else {
+        *last_print = Some(contact);
+        return;
+    };
+
+    let (kind, stride) = if protagonist.is_crawling {
+        (FootprintKind::Crawl, FOOTPRINT_CRAWL_STRIDE)
+    } else {
+        (FootprintKind::Boot, FOOTPRINT_STRIDE)
+    };
+    let step = Vec3::new(contact.x - previous.x, 0.0, contact.z - previous.z);
+    if step.length() < stride {
+        return;
+    }
+
+    let heading = step.normalize();
+    let position = if kind == FootprintKind::Boot {
+        *left_foot = !*left_foot;
+        let side = if *left_foot { -1.0 } else { 1.0 };
+        contact + heading.cross(Vec3::Y) * FOOTPRINT_FOOT_OFFSET * side
+    } else {
+        contact
+    };
+
+    spawn_footprint(&mut commands, &assets, kind, position, heading, strength, time.elapsed_seconds());
+    *last_print = Some(contact);
+}
+
+// Moving tanks leave a pair of tread marks on soft terrain
+pub fn leave_tank_tracks(
+    mut commands: Commands,
+    assets: Res<FootprintAssets>,
+    heightfield: Res<TerrainHeightfield>,
+    tank_query: Query<(Entity, &Transform, &TankTraction), With<Tank>>,
+    mut last_marks: Local<HashMap<Entity, Vec3>>,
+    time: Res<Time>,
+) {
+    for (entity, transform, traction) in tank_query.iter() {
+        let position = transform.translation;
+        let ground = heightfield.height_at(position.x, position.z);
+        let strength = heightfield.material_at(position.x, position.z).footprint_visibility();
+
+        if traction.grounded_wheels == 0
+            || strength <= 0.0
+            || position.y - ground > TANK_TRACK_MAX_CLEARANCE
+        {
+            last_marks.remove(&entity);
+            continue;
+        }
+
+        let previous = *last_marks.entry(entity).or_insert(position);
+        let step = Vec3::new(position.x - previous.x, 0.0, position.z - previous.z);
+        if step.length() < FOOTPRINT_TRACK_SPACING {
+            continue;
+        }
+
+        let heading = step.normalize();
+        let across = heading.cross(Vec3::Y) * FOOTPRINT_TRACK_OFFSET;
+        for side in [-1.0, 1.0] {
+            let mark = position + across * side;
+            let mark = Vec3::new(mark.x, heightfield.height_at(mark.x, mark.z), mark.z);
+            spawn_footprint(&mut commands, &assets, FootprintKind::TankTread, mark, heading, strength, time.elapsed_seconds());
+        }
+        last_marks.insert(entity, position);
+    }
+
+    last_marks.retain(|entity, _| tank_query.contains(*entity));
+}
+
+// Age prints, faster in strong wind and falling snow, and let them fill in
+pub fn fade_footprints(
+    mut commands: Commands,
+    assets: Res<FootprintAssets>,
+    wind: Res<WindField>,
+    weather: Res<Weather>,
+    mut footprint_query: Query<(Entity, &Transform, &mut Footprint, &mut Handle<StandardMaterial>)>,
+    time: Res<Time>,
+) {
+    let delta = time.delta_seconds() * weather.footprint_ageing();
+    let mut remaining = Vec::new();
+
+    for (entity, transform, mut footprint, mut material) in footprint_query.iter_mut() {
+        let wind_speed = wind.sample(transform.translation).length();
+        footprint.age += delta * (1.0 + wind_speed * FOOTPRINT_WIND_EROSION);
+
+        let freshness = footprint.freshness();
+        if freshness <= 0.0 {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        let faded = assets.material(freshness);
+        if *material != faded {
+            *material = faded;
+        }
+        remaining.push((entity, footprint.left_at));
+    }
+
+    // Keep the newest prints when there are too many
+    if remaining.len() > FOOTPRINT_MAX_COUNT {
+        remaining.sort_by(|a, b| a.1.total_cmp(&b.1));
+        for (entity, _) in remaining.iter().take(remaining.len() - FOOTPRINT_MAX_COUNT) {
+            commands.entity(*entity).despawn();
+        }
+    }
+}
+
+// A searching sentry that comes across a fresh trail follows it toward the newest prints
+pub fn sentries_follow_footprints(
+    footprint_query: Query<(&Transform, &Footprint)>,
+    mut sentry_query: Query<(&Transform, &mut Sentry), Without<SentryStunned>>,
+    mut message_display: ResMut<MessageDisplay>,
+) {
+    for (sentry_transform, mut sentry) in sentry_query.iter_mut() {
+        if sentry.last_heard.is_none() && sentry.tracking.is_none() {
+            continue;
+        }
+
+        let followed = sentry.tracking.unwrap_or(f32::MIN);
+        let newest = footprint_query.iter()
+            .filter(|(transform, footprint)| {
+                footprint.left_at > followed
+                    && footprint.freshness() >= FOOTPRINT_MIN_TRACKABLE
+                    && transform.translation.distance(sentry_transform.translation) <= FOOTPRINT_TRACK_RADIUS
+            })
+            .max_by(|a, b| a.1.left_at.total_cmp(&b.1.left_at));
+
+        if let Some((transform, footprint)) = newest {
+            if sentry.tracking.is_none() {
+                display_message("A SENTRY PICKED UP YOUR TRAIL", Color::srgb(1.0, 0.5, 0.0), &mut message_display);
+            }
+            sentry.last_heard = Some(transform.translation);
+            sentry.tracking = Some(footprint.left_at);
+        } else if sentry.last_heard.is_none() {
+            // Reached the last print it could find
+            sentry.tracking = None;
+        }
+    }
+}
@@ -0,0 +1,517 @@
+use bevy::prelude::*;
+use avian3d::prelude::*;
+use crate::components::{Protagonist, Inventory};
+use crate::systems::core::data_file::read_data_file;
+use crate::systems::core::screenplay::{MessageDisplay, display_message};
+use crate::systems::core::sentry::NoiseEvent;
+use crate::systems::environments::reactor::PlantChargeEvent;
+
+// Item definitions and pickup placements live here so designers can add items without code changes.
+// Relative to the assets folder.
+const ITEM_DATA_PATH: &str = "data/items.txt";
+
+// Pickup constants
+const PICKUP_RADIUS: f32 = 6.0;
+const PICKUP_SIZE: f32 = 1.5;
+const PICKUP_SPIN_SPEED: f32 = 1.5;  // Radians per second
+const PICKUP_BOB_HEIGHT: f32 = 0.5;
+const PICKUP_BOB_SPEED: f32 = 2.0;
+
+// Thrown item constants
+const THROW_SPEED: f32 = 30.0;
+const THROW_LIFT: f32 = 12.0;
+const THROW_SPAWN_OFFSET: Vec3 = Vec3::new(0.0, 4.0, 0.0);
+const THROWN_ITEM_RADIUS: f32 = 0.4;
+
+// Flare constants
+const FLARE_BURN_TIME: f32 = 45.0;
+const FLARE_INTENSITY: f32 = 2_000_000.0;
+const FLARE_RANGE: f32 = 120.0;
+const FLARE_FLICKER_SPEED: f32 = 25.0;
+
+// Noise maker constants
+const NOISE_MAKER_FUSE: f32 = 3.0;
+const NOISE_MAKER_RADIUS: f32 = 600.0;
+
+// What happens when the selected item is used (C key)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ItemEffect {
+    Charge,
+    Flare,
+    Noise,
+    Keycard,  // Passive, checked by doors
+}
+
+impl ItemEffect {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "charge" => Some(ItemEffect::Charge),
+            "flare" => Some(ItemEffect::Flare),
+            "noise" => Some(ItemEffect::Noise),
+            "keycard" => Some(ItemEffect::Keycard),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ItemDefinition {
+    pub id: String,
+    pub name: String,
+    pub max_stack: u32,
+    pub effect: ItemEffect,
+    pub color: Color,
+}
+
+#[derive(Clone, Debug)]
+pub struct PickupPlacement {
+    pub item: String,
+    pub count: u32,
+    pub position: Vec3,
+}
+
+#[derive(Resource, Default)]
+pub struct ItemCatalog {
+    pub items: Vec<ItemDefinition>,
+    pub pickups: Vec<PickupPlacement>,
+}
+
+impl ItemCatalog {
+    pub fn get(&self, id: &str) -> Option<&ItemDefinition> {
+        self.items.iter().find(|item| item.id == id)
+    }
+
+    // Parse the `|` separated catalog format, skipping (and logging) bad lines
+    fn parse(source: &str) -> Self {
+        let mut catalog = ItemCatalog::default();
+
+        for (line_number, line) in source.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let fields: Vec<&str> = line.split('|').map(str::trim).collect();
+            let parsed = match fields.as_slice() {
+                ["item", id, name, max_stack, effect, color] => {
+                    parse_item(id, name, max_stack, effect, color).map(|item| catalog.items.push(item))
+                }
+                ["pickup", item, count, position] => {
+                    parse_pickup(item, count, position).map(|pickup| catalog.pickups.push(pickup))
+                }
+                _ => None,
+            };
+
+            if parsed.is_none() {
+                warn!("{}:{}: could not parse '{}'", ITEM_DATA_PATH, line_number + 1, line);
+            }
+        }
+
+        catalog
+    }
+}
+
+fn parse_floats<const N: usize>(value: &str) -> Option<[f32; N]> {
+    let values: Vec<f32> = value
+        .split(',')
+        .map(|part| part.trim().parse().ok())
+        .collect::<Option<_>>()?;
+    values.try_into().ok()
+}
+
+fn parse_item(id: &str, name: &str, max_stack: &str, effect: &str, color: &str) -> Option<ItemDefinition> {
+    let [r, g, b] = parse_floats::<3>(color)?;
+    Some(ItemDefinition {
+        id: id.to_string(),
+        name: name.to_string(),
+        max_stack: max_stack.parse().ok().filter(|max| *max > 0)?,
+        effect: ItemEffect::parse(effect)?,
+        color: Color::srgb(r, g, b),
+    })
+}
+
+fn parse_pickup(item: &str, count: &str, position: &str) -> Option<PickupPlacement> {
+    let [x, y, z] = parse_floats::<3>(position)?;
+    Some(PickupPlacement {
+        item: item.to_string(),
+        count: count.parse().ok().filter(|count| *count > 0)?,
+        position: Vec3::new(x, y, z),
+    })
+}
+
+#[derive(Component)]
+pub struct Pickup {
+    pub item: String,
+    pub count: u32,
+    base_height: f32,
+}
+
+#[derive(Component)]
+pub struct Flare {
+    timer: Timer,
+}
+
+#[derive(Component)]
+pub struct NoiseMaker {
+    fuse: Timer,
+}
+
+#[derive(Component)]
+pub struct InventoryHudText;
+
+pub fn load_item_catalog(mut commands: Commands) {
+    let catalog = read_data_file(ITEM_DATA_PATH)
+        .map(|source| ItemCatalog::parse(&source))
+        .unwrap_or_default();
+
+    info!("Loaded {} items and {} pickups", catalog.items.len(), catalog.pickups.len());
+    commands.insert_resource(catalog);
+}
+
+pub fn spawn_pickups(
+    mut commands: Commands,
+    catalog: Res<ItemCatalog>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let mesh = meshes.add(Cuboid::new(PICKUP_SIZE, PICKUP_SIZE, PICKUP_SIZE));
+
+    for placement in &catalog.pickups {
+        let Some(definition) = catalog.get(&placement.item) else {
+            warn!("Pickup references unknown item '{}'", placement.item);
+            continue;
+        };
+
+        commands.spawn((
+            PbrBundle {
+                mesh: mesh.clone(),
+                material: materials.add(StandardMaterial {
+                    base_color: definition.color,
+                    emissive: LinearRgba::from(definition.color) * 5.0,
+                    ..default()
+                }),
+                transform: Transform::from_translation(placement.position),
+                ..default()
+            },
+            Pickup {
+                item: placement.item.clone(),
+                count: placement.count,
+                base_height: placement.position.y,
+            },
+            Name::new(format!("Pickup {}", definition.name)),
+        ));
+    }
+}
+
+pub fn setup_inventory_hud(mut commands: Commands) {
+    commands.spawn((
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font_size: 22.0,
+                color: Color::WHITE,
+                ..default()
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(20.0),
+            left: Val::Px(20.0),
+            ..default()
+        }),
+        InventoryHudText,
+    ));
+}
+
+pub fn animate_pickups(
+    time: Res<Time>,
+    mut pickup_query: Query<(&mut Transform, &Pickup)>,
+) {
+    for (mut transform, pickup) in pickup_query.iter_mut() {
+        transform.rotate_y(PICKUP_SPIN_SPEED * time.delta_seconds());
+        transform.translation.y = pickup.base_height
+            + (time.elapsed_seconds() * PICKUP_BOB_SPEED).sin() * PICKUP_BOB_HEIGHT;
+    }
+}
+
+pub fn collect_pickups(
+    mut commands: Commands,
+    catalog: Res<ItemCatalog>,
+    mut protagonist_query: Query<(&Transform, &mut Inventory), With<Protagonist>>,
+    mut pickup_query: Query<(Entity, &Transform, &mut Pickup), Without<Protagonist>>,
+    mut message_display: ResMut<MessageDisplay>,
+) {
+    let Ok((protagonist_transform, mut inventory)) = protagonist_query.get_single_mut() else {
+        return;
+    };
+
+    for (entity, pickup_transform, mut pickup) in pickup_query.iter_mut() {
+        if protagonist_transform.translation.distance(pickup_transform.translation) > PICKUP_RADIUS {
+            continue;
+        }
+
+        let Some(definition) = catalog.get(&pickup.item) else {
+            continue;
+        };
+
+        let leftover = inventory.add(&definition.id, pickup.count, definition.max_stack);
+        let taken = pickup.count - leftover;
+        if taken == 0 {
+            continue;  // Stack is full, leave the pickup where it is
+        }
+
+        display_message(
+            format!("PICKED UP {} ({}/{})", definition.name, inventory.count(&definition.id), definition.max_stack),
+            Color::WHITE,
+            &mut message_display,
+        );
+
+        if leftover == 0 {
+            commands.entity(entity).despawn_recursive();
+        } else {
+            pickup.count = leftover;
+        }
+    }
+}
+
+// R cycles the selected item, C uses it
+pub fn use_selected_item(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    catalog: Res<ItemCatalog>,
+    mut protagonist_query: Query<(&Transform, &Protagonist, &mut Inventory)>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut message_display: ResMut<MessageDisplay>,
+    mut charge_events: EventWriter<PlantChargeEvent>,
+) {
+    let Ok((transform, protagonist, mut inventory)) = protagonist_query.get_single_mut() else {
+        return;
+    };
+
+    if keyboard_input.just_pressed(KeyCode::KeyR) {
+        inventory.select_next();
+    }
+
+    if !keyboard_input.just_pressed(KeyCode::KeyC) || protagonist.is_driving || protagonist.is_dirigible {
+        return;
+    }
+
+    let Some(definition) = inventory.selected_item().and_then(|id| catalog.get(id)).cloned() else {
+        return;
+    };
+
+    let spawn_position = transform.translation + THROW_SPAWN_OFFSET + transform.forward() * 2.0;
+    let throw_velocity = transform.forward() * THROW_SPEED + Vec3::Y * THROW_LIFT;
+
+    match definition.effect {
+        ItemEffect::Flare => {
+            commands.spawn((
+                RigidBody::Dynamic,
+                Collider::sphere(THROWN_ITEM_RADIUS),
+                LinearVelocity(throw_velocity),
+                PbrBundle {
+                    mesh: meshes.add(Sphere::new(THROWN_ITEM_RADIUS)),
+                    material: materials.add(StandardMaterial {
+                        base_color: definition.color,
+                        emissive: LinearRgba::from(definition.color) * 50.0,
+                        ..default()
+                    }),
+                    transform: Transform::from_translation(spawn_position),
+                    ..default()
+                },
+                Flare {
+                    timer: Timer::from_seconds(FLARE_BURN_TIME, TimerMode::Once),
+                },
+                Name::new("Flare"),
+            )).with_children(|parent| {
+                parent.spawn(PointLightBundle {
+                    point_light: PointLight {
+                        intensity: FLARE_INTENSITY,
+                        color: definition.color,
+                        range: FLARE_RANGE,
+                        shadows_enabled: false,
+                        ..default()
+                    },
+                    ..default()
+                });
+            });
+        }
+        ItemEffect::Noise => {
+            commands.spawn((
+                RigidBody::Dynamic,
+                Collider::sphere(THROWN_ITEM_RADIUS),
+                LinearVelocity(throw_velocity),
+                PbrBundle {
+                    mesh: meshes.add(Sphere::new(THROWN_ITEM_RADIUS)),
+                    material: materials.add(StandardMaterial {
+                        base_color: definition.color,
+                        ..default()
+                    }),
+                    transform: Transform::from_translation(spawn_position),
+                    ..default()
+                },
+                NoiseMaker {
+                    fuse: Timer::from_seconds(NOISE_MAKER_FUSE, TimerMode::Once),
+                },
+                Name::new("Noise Maker"),
+            ));
+        }
+        ItemEffect::Charge => {
+            // The reactor checks the surface and takes the charge from the inventory
+            charge_events.send(PlantChargeEvent { item: definition.id });
+            return;
+        }
+        ItemEffect::Keycard => {
+            display_message(format!("{} opens locked doors", definition.name), Color::WHITE, &mut message_display);
+            return;
+        }
+    }
+
+    inventory.remove(&definition.id, 1);
+}
+
+pub fn update_flares(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut flare_query: Query<(Entity, &mut Flare, &Children)>,
+    mut light_query: Query<&mut PointLight>,
+) {
+    for (entity, mut flare, children) in flare_query.iter_mut() {
+        flare.timer.tick(time.delta());
+        if flare.timer.finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        // Flicker, and fade over the last part of the burn
+        let flicker = 0.8 + 0.2 * (time.elapsed_seconds() * FLARE_FLICKER_SPEED).sin();
+        let fade = (flare.timer.remaining_secs() / (FLARE_BURN_TIME * 0.2)).min(1.0);
+        for &child in children.iter() {
+            if let Ok(mut light) = light_query.get_mut(child) {
+                light.intensity = FLARE_INTENSITY * flicker * fade;
+            }
+        }
+    }
+}
+
+pub fn update_noise_makers(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut noise_maker_query: Query<(Entity, &Transform, &mut NoiseMaker)>,
+    mut noise_events: EventWriter<NoiseEvent>,
+) {
+    for (entity, transform, mut noise_maker) in noise_maker_query.iter_mut() {
+        if noise_maker.fuse.tick(time.delta()).just_finished() {
+            noise_events.send(NoiseEvent {
+                position: transform.translation,
+                radius: NOISE_MAKER_RADIUS,
+            });
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+pub fn update_inventory_hud(
+    catalog: Res<ItemCatalog>,
+    inventory_query: Query<&Inventory, (With<Protagonist>, Changed<Inventory>)>,
+    mut text_query: Query<&mut Text, With<InventoryHudText>>,
+) {
+    let Ok(inventory) = inventory_query.get_single() else {
+        return;
+    };
+    let Ok(mut text) = text_query.get_single_mut() else {
+        return;
+    };
+
+    text.sections = inventory
+        .stacks
+        .iter()
+        .enumerate()
+        .map(|(index, stack)| {
+            let (name, color) = catalog
+                .get(&stack.item)
+                .map_or((stack.item.as_str(), Color::WHITE), |item| (item.name.as_str(), item.color));
+            let selected = index == inventory.selected;
+            TextSection::new(
+                if selected {
+                    format!("[{} x{}]  ", name, stack.count)
+                } else {
+                    format!(" {} x{}   ", name, stack.count)
+                },
+                TextStyle {
+                    font_size: 22.0,
+                    color: if selected { color } else { color.with_alpha(0.5) },
+                    ..default()
+                },
+            )
+        })
+        .collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_items_and_pickups() {
+        let catalog = ItemCatalog::parse(
+            "# comment\n\
+             \n\
+             item | flare | FLARE | 6 | flare | 1.0,0.1,0.1\n\
+             pickup | flare | 3 | 215.0, 3.0, -45.0\n",
+        );
+
+        assert_eq!(catalog.items.len(), 1);
+        let flare = catalog.get("flare").unwrap();
+        assert_eq!(flare.name, "FLARE");
+        assert_eq!(flare.max_stack, 6);
+        assert_eq!(flare.effect, ItemEffect::Flare);
+
+        assert_eq!(catalog.pickups.len(), 1);
+        assert_eq!(catalog.pickups[0].item, "flare");
+        assert_eq!(catalog.pickups[0].count, 3);
+        assert_eq!(catalog.pickups[0].position, Vec3::new(215.0, 3.0, -45.0));
+    }
+
+    #[test]
+    fn skips_bad_lines() {
+        let catalog = ItemCatalog::parse(
+            "item | charge | CHARGE | 0 | charge | 1,1,1\n\
+             item | gadget | GADGET | 1 | teleport | 1,1,1\n\
+             item | card | CARD | 1 | keycard | 1,1\n\
+             pickup | card | many | 0,0,0\n\
+             pickup | card | 0 | 0,0,0\n\
+             nonsense\n\
+             item | card | CARD | 1 | keycard | 0.2,1.0,0.3\n",
+        );
+
+        assert_eq!(catalog.items.len(), 1);
+        assert_eq!(catalog.items[0].id, "card");
+        assert!(catalog.pickups.is_empty());
+    }
+
+    #[test]
+    fn adding_nothing_leaves_no_empty_stack() {
+        let mut inventory = Inventory::default();
+        assert_eq!(inventory.add("flare", 0, 6), 0);
+        assert!(inventory.stacks.is_empty());
+
+        assert_eq!(inventory.add("flare", 0, 0), 0);
+        assert_eq!(inventory.add("card", 2, 0), 2);
+        assert!(inventory.stacks.is_empty());
+
+        assert_eq!(inventory.add("flare", 8, 6), 2);
+        assert_eq!(inventory.add("flare", 1, 6), 1);
+        assert_eq!(inventory.stacks.len(), 1);
+        assert_eq!(inventory.count("flare"), 6);
+    }
+
+    #[test]
+    fn shipped_catalog_parses() {
+        let source = read_data_file(ITEM_DATA_PATH).unwrap();
+        let catalog = ItemCatalog::parse(&source);
+        assert!(!catalog.items.is_empty());
+        assert!(catalog.pickups.iter().all(|pickup| catalog.get(&pickup.item).is_some()));
+    }
+}
@@ -3,4 +3,6 @@ pub mod falling;
 pub mod swimming;
 pub mod teleports;
 pub mod driving;
-pub mod dirigible;
\ No newline at end of file
+pub mod dirigible;
+pub mod inventory;
+pub mod footprints;
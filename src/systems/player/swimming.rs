@@ -1,9 +1,10 @@
 use bevy::prelude::*;
 use crate::components::Protagonist;
+use crate::systems::environments::lighting::{LightingStack, LightingZone};
 
 pub fn swimming_system(
     mut protagonist_query: Query<(Entity, &Transform, &mut Protagonist)>,
-    mut ambient_light: ResMut<AmbientLight>,
+    mut lighting: ResMut<LightingStack>,
 ) {
     for (_entity, transform, mut protagonist) in protagonist_query.iter_mut() {
         if transform.translation.y < -5.0 {
@@ -12,14 +13,12 @@ pub fn swimming_system(
             protagonist.is_falling = false;
             protagonist.is_climbing = false;
             protagonist.is_dirigible = false;
-            ambient_light.color = Color::srgb(0.0, 0.2, 0.4);
-            ambient_light.brightness = 100.0;
+            lighting.push(LightingZone::Underwater, 1.0);
         } else {
             if protagonist.is_swimming {
                 protagonist.is_swimming = false;
                 protagonist.was_swimming = true;
-                ambient_light.color = Color::srgb(0.1, 0.1, 0.3);
-                ambient_light.brightness = 100.0;
+                lighting.pop(LightingZone::Underwater);
             }
         }
     }